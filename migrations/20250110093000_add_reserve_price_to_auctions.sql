@@ -0,0 +1 @@
+ALTER TABLE auctions ADD COLUMN reserve_price INTEGER;
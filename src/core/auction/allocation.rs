@@ -0,0 +1,114 @@
+use crate::core::domain::Bid;
+
+/// The most allocations kept on the frontier. Past it the frontier is thinned evenly, which
+/// trades exactness for a bound on the work done per bid.
+const MAX_FRONTIER_LEN: usize = 1024;
+
+/// A partial allocation tracked while searching for the revenue-maximizing set of bids.
+#[derive(Clone)]
+struct Allocation {
+    /// Total blockspace consumed by the selected bids
+    size: u64,
+
+    /// Total revenue of the selected bids
+    value: u64,
+
+    /// Indices of the selected bids, in the order they were considered
+    selected: Vec<usize>,
+}
+
+/// Selects the set of bids that maximizes the total bid amount without exceeding `capacity`.
+///
+/// This is a 0/1 knapsack solved over the Pareto frontier of (size, value) pairs, so the cost
+/// depends on the number of distinct useful allocations rather than on `capacity` itself. The
+/// frontier is capped at `MAX_FRONTIER_LEN` allocations; below that the result is exact.
+/// Bids are considered in the given order. Among allocations of equal value the smaller one is
+/// kept, and among allocations of equal size the one found first, so the result is deterministic
+/// for a given order.
+///
+/// Returns the indices of the selected bids in ascending order.
+pub fn allocate_blockspace(bids: &[Bid], capacity: u64) -> Vec<usize> {
+    best_allocation(bids, capacity, None).selected
+}
+
+/// Returns the maximum total bid amount that fits into `capacity`, optionally ignoring one bid.
+pub fn max_revenue(bids: &[Bid], capacity: u64, excluded: Option<usize>) -> u64 {
+    best_allocation(bids, capacity, excluded).value
+}
+
+/// Returns how much revenue the given bids lose if `reserved_size` of the blockspace is taken
+/// away from them, e.g., by the leg of a package bid.
+pub fn displaced_revenue(bids: &[Bid], capacity: u64, reserved_size: u64) -> u64 {
+    let remaining = capacity.saturating_sub(reserved_size);
+    max_revenue(bids, capacity, None).saturating_sub(max_revenue(bids, remaining, None))
+}
+
+fn best_allocation(bids: &[Bid], capacity: u64, excluded: Option<usize>) -> Allocation {
+    // Sorted by size, with strictly increasing value
+    let mut frontier = vec![Allocation {
+        size: 0,
+        value: 0,
+        selected: Vec::new(),
+    }];
+
+    for (index, bid) in bids.iter().enumerate() {
+        if Some(index) == excluded || bid.blockspace_used() > capacity {
+            continue;
+        }
+
+        let extended: Vec<Allocation> = frontier
+            .iter()
+            .filter(|allocation| allocation.size <= capacity - bid.blockspace_used())
+            .map(|allocation| {
+                let mut selected = allocation.selected.clone();
+                selected.push(index);
+                Allocation {
+                    size: allocation.size + bid.blockspace_used(),
+                    value: allocation.value.saturating_add(bid.effective_value()),
+                    selected,
+                }
+            })
+            .collect();
+
+        // Existing allocations come first so that they win ties against the extended ones
+        let mut candidates = frontier;
+        candidates.extend(extended);
+        candidates.sort_by_key(|allocation| allocation.size);
+
+        // Drop every allocation that is dominated by a smaller (or earlier) one
+        frontier = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let dominated = frontier
+                .last()
+                .is_some_and(|best: &Allocation| best.value >= candidate.value);
+            if !dominated {
+                frontier.push(candidate);
+            }
+        }
+        frontier = thin(frontier);
+    }
+
+    // The frontier has strictly increasing value, so the last allocation is the best one
+    frontier.pop().unwrap_or(Allocation {
+        size: 0,
+        value: 0,
+        selected: Vec::new(),
+    })
+}
+
+/// Keeps every `stride`-th allocation of an oversized frontier, always including the last (most
+/// valuable) one, so that at most `MAX_FRONTIER_LEN` remain.
+fn thin(frontier: Vec<Allocation>) -> Vec<Allocation> {
+    if frontier.len() <= MAX_FRONTIER_LEN {
+        return frontier;
+    }
+
+    let last = frontier.len() - 1;
+    let stride = frontier.len().div_ceil(MAX_FRONTIER_LEN - 1);
+    frontier
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| index % stride == 0 || *index == last)
+        .map(|(_, allocation)| allocation)
+        .collect()
+}
@@ -1,23 +1,33 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use tokio::{
-    sync::{mpsc, RwLock},
+    sync::{broadcast, mpsc, Mutex, RwLock},
     task::JoinHandle,
 };
 
 use crate::{
     core::{
-        auction::AuctionWorker,
+        auction::{allocation, AuctionWorker},
         domain::{
-            AuctionId, AuctionInfo, AuctionState, Bid, ChainId, Tx, WorkerMessage,
-            WorkerMessageType,
+            AuctionCancellation, AuctionEvent, AuctionId, AuctionInfo, AuctionMode, AuctionPhase,
+            AuctionResult, AuctionState, Bid, BidCancellation, BidCommitment, BidLimits,
+            BidRemoval, ChainId, CrossChainBid, EncryptedBid, OrderedTx, PackageBid,
+            SignatureScheme, Tx, WorkerMessage, WorkerMessageType,
         },
     },
-    services::registry::{AuctionRegistry, RegistryService},
+    services::{
+        audit::{AuditEvent, AuditLog},
+        operator::OperatorService,
+        registry::{AuctionRegistry, ChainRegistry, RegistryService},
+    },
     utils::{
         errors::AuctionError,
         helpers::current_unix_ms,
-        types::{ArcRwLock, ArcRwLockHashMap},
+        merkle::{MerkleProof, MerkleTree},
+        types::{ArcMutexHashMap, ArcRwLock, ArcRwLockHashMap},
     },
 };
 
@@ -28,7 +38,11 @@ pub struct AuctionManager {
     /// Manages registration (scheduling) and validation logic for auctions
     pub auction_registry: ArcRwLock<AuctionRegistry>,
 
-    /// Maps a `ChainId` to the `AuctionInfo` of the ongoing auction (if any)
+    /// Provides per-chain configuration (e.g., the pricing rule) when spawning workers
+    pub chain_registry: ArcRwLock<ChainRegistry>,
+
+    /// Maps a `ChainId` to the `AuctionInfo` of the ongoing auction (if any).
+    /// Its `end_time` is kept up to date with soft-close extensions reported by the worker.
     pub ongoing_auctions: ArcRwLockHashMap<ChainId, AuctionInfo>,
 
     /// Maps a `ChainId` to its dedicated `AuctionWorker`
@@ -37,27 +51,88 @@ pub struct AuctionManager {
     /// Maps a `ChainId` to a worker's background task handle
     pub worker_handles: ArcRwLockHashMap<ChainId, JoinHandle<()>>,
 
+    /// Single-block bids received for queued auctions, validated by the chain's worker and kept
+    /// in a state of their own. The auction starts from that state, and its bids count as the
+    /// competition of package bids until then.
+    pub staged_bids: ArcRwLockHashMap<AuctionId, AuctionState>,
+
+    /// Tracks the next arrival sequence number for each auction. Shared with the workers and
+    /// `BidService`, so that every bid of an auction gets its own number, whoever stamped it.
+    pub bid_sequences: ArcMutexHashMap<AuctionId, u64>,
+
+    /// Maps an `AuctionId` to the package bids whose first leg is in that auction.
+    /// They are resolved when the auction closes, before its outcome is settled.
+    pub package_bids: ArcRwLockHashMap<AuctionId, Vec<PackageBid>>,
+
+    /// Maps an `AuctionId` to the winning package bid that was awarded its top of block
+    pub package_awards: ArcRwLockHashMap<AuctionId, PackageBid>,
+
+    /// Cross-chain bids whose legs are waiting for their auctions to close
+    cross_chain_bids: ArcRwLock<Vec<CrossChainBid>>,
+
+    /// Serializes the settlement of closed auctions with the placement of cross-chain legs, so
+    /// that the legs of a cross-chain bid are always decided together
+    settlement_lock: Arc<Mutex<()>>,
+
+    /// Maps an `AuctionId` to the signed result of the ended auction
+    pub auction_results: ArcRwLockHashMap<AuctionId, AuctionResult>,
+
+    /// Maps an `AuctionId` to the Merkle tree over the bid log of the ended auction, whose root
+    /// is published in its `AuctionResult`
+    pub bid_trees: ArcRwLockHashMap<AuctionId, MerkleTree>,
+
+    /// Maps an `AuctionId` to the Merkle tree over the removals from the ended auction's bids,
+    /// whose root is published in its `AuctionResult`
+    pub removal_trees: ArcRwLockHashMap<AuctionId, MerkleTree>,
+
+    /// Holds the operator key that signs `AuctionResult`s. It is provided by the caller, so that
+    /// the operator keeps its identity across restarts.
+    pub operator: OperatorService,
+
     /// Used by a worker to send a `WorkerMessage` when an auction event occurs
     pub message_sender: mpsc::Sender<WorkerMessage>,
+
+    /// Broadcasts `AuctionEvent`s to subscribers (e.g., `BidService` and bidders)
+    pub event_sender: broadcast::Sender<AuctionEvent>,
+
+    /// Records lifecycle events, if enabled on the `RegistryService`
+    audit_log: Option<AuditLog>,
 }
 
 impl AuctionManager {
     /// Creates a new `AuctionManager`.
     /// If desired, you could spawn a worker for every `ChainId` at creation time,
     /// or you can spawn a worker only when a new `ChainId` is introduced.
+    /// The `OperatorService` signs what the manager emits, and is typically loaded from the
+    /// operator's keystore with `OperatorService::from_keystore`.
     /// TODO: Delete RegistryService dependency
-    pub async fn new(registry_service: &RegistryService) -> Self {
+    pub async fn new(registry_service: &RegistryService, operator: OperatorService) -> Self {
         let (message_sender, mut message_receiver) = mpsc::channel(100);
+        let (event_sender, _) = broadcast::channel(100);
 
         let chain_ids = registry_service.get_chain_ids().await;
         let auction_registry = registry_service.get_auction_registry();
+        let chain_registry = registry_service.get_chain_registry();
 
         let manager = AuctionManager {
             auction_registry,
+            chain_registry,
             ongoing_auctions: Arc::new(RwLock::new(HashMap::new())),
             workers: Arc::new(RwLock::new(HashMap::new())),
             worker_handles: Arc::new(RwLock::new(HashMap::new())),
+            staged_bids: Arc::new(RwLock::new(HashMap::new())),
+            bid_sequences: Arc::new(Mutex::new(HashMap::new())),
+            package_bids: Arc::new(RwLock::new(HashMap::new())),
+            package_awards: Arc::new(RwLock::new(HashMap::new())),
+            cross_chain_bids: Arc::new(RwLock::new(Vec::new())),
+            settlement_lock: Arc::new(Mutex::new(())),
+            auction_results: Arc::new(RwLock::new(HashMap::new())),
+            bid_trees: Arc::new(RwLock::new(HashMap::new())),
+            removal_trees: Arc::new(RwLock::new(HashMap::new())),
+            operator,
             message_sender,
+            event_sender,
+            audit_log: registry_service.get_audit_log(),
         };
 
         // Clone the manager for the background task
@@ -80,11 +155,27 @@ impl AuctionManager {
     /// Creates and runs an `AuctionWorker` for a specified chain in the background.
     /// Does nothing if a worker for that chain already exists.
     pub async fn start_worker_for_chain(&self, chain_id: ChainId) {
+        let Some(worker) = self.add_worker(chain_id).await else {
+            return;
+        };
+
+        let handle = tokio::spawn(async move {
+            worker.run().await;
+        });
+
+        // Insert the worker handle
+        let mut handles_guard = self.worker_handles.write().await;
+        handles_guard.insert(chain_id, handle);
+    }
+
+    /// Creates the `AuctionWorker` of a chain without running it, and returns it.
+    /// Returns `None` if a worker for that chain already exists.
+    async fn add_worker(&self, chain_id: ChainId) -> Option<Arc<AuctionWorker>> {
         // Acquire a read lock to check if the worker already exists
         {
             let workers_guard = self.workers.read().await;
             if workers_guard.contains_key(&chain_id) {
-                return;
+                return None;
             }
         }
 
@@ -92,29 +183,38 @@ impl AuctionManager {
         let mut workers_guard = self.workers.write().await;
         if workers_guard.contains_key(&chain_id) {
             // Double-check to prevent race conditions
-            return;
+            return None;
         }
 
-        let worker = Arc::new(AuctionWorker::new(chain_id, self.message_sender.clone()));
-        workers_guard.insert(chain_id, worker.clone());
-
-        drop(workers_guard); // Release the write lock before spawning the task
-
-        let handle = tokio::spawn(async move {
-            worker.run().await;
-        });
+        // Use the auction settings and winner-selection rule configured for the chain,
+        // falling back to the defaults
+        let (chain_info, auction_rule) = {
+            let chain_registry_guard = self.chain_registry.read().await;
+            (
+                chain_registry_guard
+                    .get_chain_info(chain_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                chain_registry_guard.get_auction_rule(chain_id),
+            )
+        };
 
-        // Insert the worker handle
-        let mut handles_guard = self.worker_handles.write().await;
-        handles_guard.insert(chain_id, handle);
+        let mut worker = AuctionWorker::new(chain_id, &chain_info, self.message_sender.clone())
+            .with_bid_sequences(self.bid_sequences.clone());
+        if let Some(auction_rule) = auction_rule {
+            worker = worker.with_auction_rule(auction_rule);
+        }
+        let worker = Arc::new(worker);
+        workers_guard.insert(chain_id, worker.clone());
+        Some(worker)
     }
 
     /// Processes a finished auction (received from the worker).
-    /// This is where you could update a database, log events, send notifications, etc.
+    /// Lifecycle events are recorded in the audit log; this is also where you could update a database, send notifications, etc.
     async fn handle_worker_message(&self, message: WorkerMessage) {
         println!("[Manager] Received worker message: {:?}", message);
         match message.message_type {
-            WorkerMessageType::AuctionEnded => {
+            WorkerMessageType::AuctionClosed => {
                 let chain_id = message.chain_id;
                 let auction_id = message.auction_id.clone();
 
@@ -130,11 +230,48 @@ impl AuctionManager {
                         let mut ongoing_guard = self.ongoing_auctions.write().await;
                         ongoing_guard.remove(&chain_id);
                         println!(
-                            "[Manager] Auction with ID {} on Chain {} has ended and was removed.",
+                            "[Manager] Auction with ID {} on Chain {} has closed and was removed.",
                             auction_id, chain_id
                         );
                     }
                 }
+
+                // Settlement takes the workers' state locks, which a worker may hold while it
+                // waits for room in the message channel, so it must not block this loop
+                let manager = self.clone();
+                tokio::spawn(async move {
+                    manager.settle_auctions().await;
+                });
+            }
+            WorkerMessageType::AuctionExtended(end_time) => {
+                let mut ongoing_guard = self.ongoing_auctions.write().await;
+                if let Some(ongoing_auction_info) = ongoing_guard.get_mut(&message.chain_id) {
+                    if ongoing_auction_info.id == message.auction_id {
+                        ongoing_auction_info.end_time = end_time;
+                        println!(
+                            "[Manager] Auction with ID {} on Chain {} was extended until {}.",
+                            message.auction_id, message.chain_id, end_time
+                        );
+                    }
+                }
+                drop(ongoing_guard);
+                self.record_audit_event(AuditEvent::AuctionExtended {
+                    chain_id: message.chain_id,
+                    auction_id: message.auction_id,
+                    end_time,
+                })
+                .await;
+            }
+            WorkerMessageType::RevealStarted => {
+                println!(
+                    "[Manager] Auction with ID {} on Chain {} entered its reveal phase.",
+                    message.auction_id, message.chain_id
+                );
+                self.record_audit_event(AuditEvent::RevealStarted {
+                    chain_id: message.chain_id,
+                    auction_id: message.auction_id,
+                })
+                .await;
             }
             WorkerMessageType::AuctionProcessing => {
                 // Handle other message types if necessary
@@ -163,6 +300,21 @@ impl AuctionManager {
             return None;
         }
 
+        // The previous auction keeps the worker until it has been settled
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+        if let Some(worker) = &worker_opt {
+            if worker
+                .get_auction_state()
+                .await
+                .is_ok_and(|auction_state| auction_state.phase == AuctionPhase::Settling)
+            {
+                return None;
+            }
+        }
+
         // Step 3: Remove the next auction from the registry
         {
             let mut registry_guard = self.auction_registry.write().await;
@@ -171,16 +323,16 @@ impl AuctionManager {
 
         let auction_id = next_info.id.clone();
 
-        // Step 4: Retrieve the worker for the chain
-        let worker_opt = {
-            let workers_guard = self.workers.read().await;
-            workers_guard.get(&chain_id).cloned()
-        };
-
-        // Step 5: Start the auction
+        // Step 4: Start the auction with the bids received while it was queued
         if let Some(worker) = worker_opt {
+            let staged = self
+                .staged_bids
+                .write()
+                .await
+                .remove(&auction_id)
+                .unwrap_or_else(|| AuctionState::new(next_info.clone()));
             if worker
-                .start_auction(auction_id.clone(), next_info.clone())
+                .start_staged_auction(auction_id.clone(), staged)
                 .await
                 .is_err()
             {
@@ -189,6 +341,10 @@ impl AuctionManager {
                     auction_id, chain_id
                 );
             }
+
+            // Step 5: Hand over the package award
+            self.forward_package_award(&worker, &auction_id).await;
+            self.prune_staged_bids(chain_id).await;
             {
                 // Insert into ongoing auctions
                 let mut ongoing_guard = self.ongoing_auctions.write().await;
@@ -197,17 +353,131 @@ impl AuctionManager {
                     "[Manager] Auction {} started on Chain {}.",
                     auction_id, chain_id
                 );
-                Some(auction_id)
             }
+            self.record_audit_event(AuditEvent::AuctionStarted {
+                chain_id,
+                auction_id: auction_id.clone(),
+            })
+            .await;
+            Some(auction_id)
         } else {
             println!(
                 "[Manager] No worker found for Chain {}. Cannot start auction.",
                 chain_id
             );
+            self.prune_staged_bids(chain_id).await;
             None
         }
     }
 
+    /// Cancels a queued or running auction on behalf of its seller.
+    ///
+    /// A queued auction is removed from the `AuctionRegistry`, and a running one is aborted in
+    /// its worker without an outcome. Staged bids and package bids for the auction are dropped,
+    /// as are the awards of a package spanning it. Cross-chain bids with a leg in it lose that
+    /// leg, and the auctions their other legs were held in are settled. An
+    /// `AuctionEvent::Cancelled` is broadcast so that subscribers can discard their bids.
+    pub async fn cancel_auction(
+        &self,
+        cancellation: AuctionCancellation,
+    ) -> Result<String, AuctionError> {
+        let chain_id = cancellation.chain_id;
+        let auction_id = cancellation.auction_id.clone();
+
+        let signature_scheme = self.get_signature_scheme(chain_id).await;
+        if !signature_scheme.verifier().verify(
+            &cancellation.seller_address,
+            &cancellation.signing_hash(),
+            &cancellation.seller_signature,
+        ) {
+            return Err(AuctionError::InvalidSellerSignature);
+        }
+
+        let ongoing_auction_opt = {
+            let ongoing_guard = self.ongoing_auctions.read().await;
+            ongoing_guard
+                .get(&chain_id)
+                .filter(|auction_info| auction_info.id == auction_id)
+                .cloned()
+        };
+
+        if let Some(auction_info) = ongoing_auction_opt {
+            if auction_info.seller_address != cancellation.seller_address {
+                return Err(AuctionError::NotAuctionSeller(cancellation.seller_address));
+            }
+
+            let worker = {
+                let workers_guard = self.workers.read().await;
+                workers_guard.get(&chain_id).cloned()
+            }
+            .ok_or(AuctionError::NoAuctions)?;
+            worker.abort_auction(auction_id.clone()).await?;
+            self.ongoing_auctions.write().await.remove(&chain_id);
+        } else {
+            let mut registry_guard = self.auction_registry.write().await;
+            let auction_info = registry_guard
+                .get_queued_auctions(chain_id)
+                .into_iter()
+                .find(|auction_info| auction_info.id == auction_id)
+                .ok_or(AuctionError::InvalidAuctionId(auction_id.clone()))?;
+            if auction_info.seller_address != cancellation.seller_address {
+                return Err(AuctionError::NotAuctionSeller(cancellation.seller_address));
+            }
+            registry_guard.remove_auction(chain_id, &auction_id);
+        }
+
+        self.staged_bids.write().await.remove(&auction_id);
+        self.bid_sequences.lock().await.remove(&auction_id);
+        // A package bid needs every one of its blocks, so any package spanning the auction is void
+        {
+            let mut package_guard = self.package_bids.write().await;
+            for packages in package_guard.values_mut() {
+                packages
+                    .retain(|package| package.legs.iter().all(|leg| leg.auction_id != auction_id));
+            }
+            package_guard.retain(|_, packages| !packages.is_empty());
+        }
+        // The same goes for a package that was already awarded the auction's top of block
+        {
+            let mut awards_guard = self.package_awards.write().await;
+            if let Some(package) = awards_guard.remove(&auction_id) {
+                for leg in package.legs.iter() {
+                    awards_guard.remove(&leg.auction_id);
+                }
+            }
+        }
+        self.settle_auctions().await;
+
+        // Sending only fails when there are no subscribers, which is fine
+        let _ = self.event_sender.send(AuctionEvent::Cancelled {
+            chain_id,
+            auction_id: auction_id.clone(),
+        });
+
+        println!(
+            "[Manager] Auction {} on Chain {} was cancelled by its seller.",
+            auction_id, chain_id
+        );
+        self.record_audit_event(AuditEvent::AuctionCancelled {
+            chain_id,
+            auction_id: auction_id.clone(),
+        })
+        .await;
+        Ok(format!("[Manager] ACK: Auction {} cancelled.", auction_id))
+    }
+
+    /// Subscribes to the `AuctionEvent`s broadcast from now on.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<AuctionEvent> {
+        self.event_sender.subscribe()
+    }
+
+    /// Records a lifecycle event in the audit log, if enabled.
+    pub async fn record_audit_event(&self, event: AuditEvent) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(event).await;
+        }
+    }
+
     // ------------------------------------------------------------------------
     // Getters
     // ------------------------------------------------------------------------
@@ -220,6 +490,68 @@ impl AuctionManager {
             .map(|auction_info| auction_info.id.clone())
     }
 
+    /// Retrieves the effective end time of the ongoing auction for a given chain,
+    /// including any soft-close extensions.
+    pub async fn get_ongoing_auction_end_time(&self, chain_id: ChainId) -> Option<u64> {
+        let ongoing_guard = self.ongoing_auctions.read().await;
+        ongoing_guard
+            .get(&chain_id)
+            .map(|auction_info| auction_info.end_time)
+    }
+
+    /// Retrieves the signed result of an ended auction.
+    pub async fn get_auction_result(&self, auction_id: &AuctionId) -> Option<AuctionResult> {
+        let results_guard = self.auction_results.read().await;
+        results_guard.get(auction_id).cloned()
+    }
+
+    /// Retrieves the proof that a bid, by its hash, is included in the `bid_root` of an ended
+    /// auction's `AuctionResult`.
+    pub async fn get_bid_inclusion_proof(
+        &self,
+        auction_id: &AuctionId,
+        bid_hash: &str,
+    ) -> Result<MerkleProof, AuctionError> {
+        let trees_guard = self.bid_trees.read().await;
+        let bid_tree = trees_guard
+            .get(auction_id)
+            .ok_or_else(|| AuctionError::InvalidAuctionId(auction_id.clone()))?;
+        bid_tree
+            .proof(bid_hash.as_bytes())
+            .ok_or_else(|| AuctionError::BidNotFound(bid_hash.to_string()))
+    }
+
+    /// Retrieves the proof that a submission left an ended auction for the given reason, against
+    /// the `removal_root` of its `AuctionResult`.
+    pub async fn get_bid_removal_proof(
+        &self,
+        auction_id: &AuctionId,
+        removal: &BidRemoval,
+    ) -> Result<MerkleProof, AuctionError> {
+        let trees_guard = self.removal_trees.read().await;
+        let removal_tree = trees_guard
+            .get(auction_id)
+            .ok_or_else(|| AuctionError::InvalidAuctionId(auction_id.clone()))?;
+        removal_tree
+            .proof(removal.hash().as_bytes())
+            .ok_or_else(|| AuctionError::BidNotFound(removal.submission_hash.clone()))
+    }
+
+    /// Retrieves the address of the active operator key, which signs `AuctionResult`s.
+    pub async fn get_operator_address(&self) -> String {
+        self.operator.address().await
+    }
+
+    /// Retrieves the IDs of the auctions queued for a given chain, ordered from next to last.
+    pub async fn get_queued_auction_ids(&self, chain_id: ChainId) -> Vec<AuctionId> {
+        let registry_guard = self.auction_registry.read().await;
+        registry_guard
+            .get_queued_auctions(chain_id)
+            .into_iter()
+            .map(|auction_info| auction_info.id)
+            .collect()
+    }
+
     pub async fn get_all_ongoing_auction_ids(&self) -> HashMap<ChainId, AuctionId> {
         let ongoing_guard = self.ongoing_auctions.read().await;
         ongoing_guard
@@ -239,6 +571,20 @@ impl AuctionManager {
         auction_id: AuctionId,
         bid: Bid,
     ) -> Result<String, AuctionError> {
+        if self.is_queued_auction(chain_id, &auction_id).await {
+            if let Some(e) = self
+                .stage_bids(chain_id, &auction_id, vec![bid], false, Vec::new())
+                .await?
+                .pop()
+            {
+                return Err(e);
+            }
+            return Ok(format!(
+                "[Manager] ACK: Bid staged for queued auction {}.",
+                auction_id
+            ));
+        }
+
         let worker_opt = {
             let workers_guard = self.workers.read().await;
             workers_guard.get(&chain_id).cloned()
@@ -251,52 +597,1549 @@ impl AuctionManager {
         }
     }
 
-    pub async fn submit_bid_batch(
+    /// Submits a batch of bids flushed by `BidService`, which stamped them with their arrival
+    /// sequence numbers and times, along with the receipted bids it dropped from its buffer
+    /// (see `AuctionWorker::submit_bid_batch`).
+    pub(crate) async fn submit_bid_batch(
         &self,
         chain_id: ChainId,
         auction_id: AuctionId,
         bids: Vec<Bid>,
+        dropped: Vec<BidRemoval>,
     ) -> Result<(), AuctionError> {
+        if self.is_queued_auction(chain_id, &auction_id).await {
+            // Invalid bids are dropped from the batch, as by the worker
+            let rejected = self
+                .stage_bids(chain_id, &auction_id, bids, true, dropped)
+                .await?;
+            if !rejected.is_empty() {
+                eprintln!(
+                    "[Manager] Dropped {} invalid bid(s) for queued auction {}",
+                    rejected.len(),
+                    auction_id
+                );
+            }
+            return Ok(());
+        }
+
         let worker_opt = {
             let workers_guard = self.workers.read().await;
             workers_guard.get(&chain_id).cloned()
         };
 
         if let Some(worker) = worker_opt {
-            worker.submit_bid_batch(auction_id, bids).await
+            worker.submit_bid_batch(auction_id, bids, dropped).await
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    /// Requests the latest ToB (Top-of-Block) info for the current auction.
-    pub async fn request_latest_tob(&self, chain_id: ChainId) -> Result<Vec<Tx>, AuctionError> {
+    /// Submits an all-or-nothing bid on the top of consecutive blocks of one chain.
+    ///
+    /// The legs must be in block order and reference the ongoing auction or auctions queued in
+    /// `AuctionRegistry`. The package is resolved when the auction of its first leg closes: it wins
+    /// only if its total amount beats the revenue the competing single-block bids would lose
+    /// to its legs across all of its auctions.
+    pub async fn submit_package_bid(&self, package: PackageBid) -> Result<String, AuctionError> {
+        let chain_id = package.chain_id;
+        let auction_mode = {
+            let chain_registry_guard = self.chain_registry.read().await;
+            chain_registry_guard
+                .get_chain_info(chain_id)
+                .ok_or(AuctionError::InvalidChainId(chain_id))?
+                .auction_mode
+        };
+        if auction_mode != AuctionMode::SealedBid {
+            return Err(AuctionError::PackageBidNotAccepted);
+        }
+
+        if package.legs.len() < 2 {
+            return Err(AuctionError::InvalidPackageBid(
+                "a package needs legs in at least two auctions".to_string(),
+            ));
+        }
+
+        let signature_scheme = self.get_signature_scheme(package.chain_id).await;
+        if !signature_scheme.verifier().verify(
+            &package.bidder_addr,
+            &package.signing_hash(),
+            &package.bidder_signature,
+        ) {
+            return Err(AuctionError::InvalidBuyerSignature);
+        }
+
+        // Auctions a leg may reference: the ongoing one, followed by the queued ones
+        let mut auctions: Vec<AuctionInfo> = {
+            let ongoing_guard = self.ongoing_auctions.read().await;
+            ongoing_guard.get(&chain_id).cloned().into_iter().collect()
+        };
+        auctions.extend(
+            self.auction_registry
+                .read()
+                .await
+                .get_queued_auctions(chain_id),
+        );
+
+        let mut previous_block: Option<u64> = None;
+        for leg in package.legs.iter() {
+            if leg.chain_id != chain_id || leg.bidder_addr != package.bidder_addr {
+                return Err(AuctionError::InvalidPackageBid(
+                    "every leg must be on the package's chain and from its bidder".to_string(),
+                ));
+            }
+
+            let auction_info = auctions
+                .iter()
+                .find(|info| info.id == leg.auction_id)
+                .ok_or(AuctionError::InvalidAuctionId(leg.auction_id.clone()))?;
+
+            if previous_block.is_some_and(|block| auction_info.block_number != block + 1) {
+                return Err(AuctionError::InvalidPackageBid(
+                    "legs must cover consecutive blocks in order".to_string(),
+                ));
+            }
+            previous_block = Some(auction_info.block_number);
+
+            if leg.price_per_gas == Some(0) {
+                return Err(AuctionError::ZeroPricePerGas);
+            }
+
+            if leg.blockspace_used() > auction_info.blockspace_size {
+                return Err(AuctionError::InvalidPackageBid(format!(
+                    "leg for auction {} exceeds its blockspace",
+                    leg.auction_id
+                )));
+            }
+            if let Some(reserve_price) = auction_info.reserve_price {
+                if leg.effective_value() < reserve_price {
+                    return Err(AuctionError::BidBelowReservePrice {
+                        bid_amount: leg.effective_value(),
+                        reserve_price,
+                    });
+                }
+            }
+        }
+
+        if package.total_amount().is_none() {
+            return Err(AuctionError::InvalidPackageBid(
+                "the total amount of the legs overflows".to_string(),
+            ));
+        }
+
+        let first_auction_id = package.legs[0].auction_id.clone();
+        let event = AuditEvent::PackageBidAccepted {
+            chain_id,
+            bidder_addr: package.bidder_addr.clone(),
+            leg_hashes: package.legs.iter().map(Bid::hash).collect(),
+        };
+        self.package_bids
+            .write()
+            .await
+            .entry(first_auction_id.clone())
+            .or_default()
+            .push(package);
+        self.record_audit_event(event).await;
+
+        Ok(format!(
+            "[Manager] ACK: Package bid starting at auction {} accepted.",
+            first_auction_id
+        ))
+    }
+
+    /// Submits an all-or-nothing bid on the ongoing auctions of two or more chains.
+    ///
+    /// Each leg is submitted to its chain's worker and competes there as an ordinary bid.
+    /// An auction holding a leg that closes is not ended until all of the legs' auctions have
+    /// closed. The legs are then decided together: if any leg lost, the legs that won are
+    /// withdrawn, and only then are the auctions ended and their results signed.
+    pub async fn submit_cross_chain_bid(
+        &self,
+        cross_chain_bid: CrossChainBid,
+    ) -> Result<String, AuctionError> {
+        let legs = &cross_chain_bid.legs;
+        let chain_ids: HashSet<ChainId> = legs.iter().map(|leg| leg.chain_id).collect();
+        if legs.len() < 2 || chain_ids.len() != legs.len() {
+            return Err(AuctionError::InvalidCrossChainBid(
+                "a cross-chain bid needs exactly one leg on each of at least two chains"
+                    .to_string(),
+            ));
+        }
+        if legs
+            .iter()
+            .any(|leg| leg.bidder_addr != cross_chain_bid.bidder_addr)
+        {
+            return Err(AuctionError::InvalidCrossChainBid(
+                "every leg must be from the bid's bidder".to_string(),
+            ));
+        }
+        // The signature must be valid under the scheme of every chain, so the chains of a
+        // cross-chain bid need to share a scheme
+        let signing_hash = cross_chain_bid.signing_hash();
+        for &chain_id in chain_ids.iter() {
+            let signature_scheme = self.get_signature_scheme(chain_id).await;
+            if !signature_scheme.verifier().verify(
+                &cross_chain_bid.bidder_addr,
+                &signing_hash,
+                &cross_chain_bid.bidder_signature,
+            ) {
+                return Err(AuctionError::InvalidBuyerSignature);
+            }
+        }
+
+        // Every leg must go to the ongoing auction of a sealed-bid chain
+        let mut workers = Vec::with_capacity(legs.len());
+        for leg in legs {
+            let auction_mode = {
+                let chain_registry_guard = self.chain_registry.read().await;
+                chain_registry_guard
+                    .get_chain_info(leg.chain_id)
+                    .ok_or(AuctionError::InvalidChainId(leg.chain_id))?
+                    .auction_mode
+            };
+            if auction_mode != AuctionMode::SealedBid {
+                return Err(AuctionError::InvalidCrossChainBid(format!(
+                    "chain {} does not run sealed-bid auctions",
+                    leg.chain_id
+                )));
+            }
+            if self.get_ongoing_auction_id(leg.chain_id).await.as_ref() != Some(&leg.auction_id) {
+                return Err(AuctionError::InvalidAuctionId(leg.auction_id.clone()));
+            }
+
+            let worker = {
+                let workers_guard = self.workers.read().await;
+                workers_guard.get(&leg.chain_id).cloned()
+            }
+            .ok_or(AuctionError::NoAuctions)?;
+            workers.push(worker);
+        }
+
+        // Submit all legs, reverting the ones already placed if any is rejected. No auction is
+        // settled meanwhile, so the reverted legs never count towards an outcome.
+        let _settlement = self.settlement_lock.lock().await;
+        let mut placements = Vec::with_capacity(legs.len());
+        for (leg, worker) in legs.iter().zip(workers.iter()) {
+            match worker
+                .submit_cross_chain_leg(leg.auction_id.clone(), leg.clone())
+                .await
+            {
+                Ok(placement) => placements.push(placement),
+                Err(e) => {
+                    for (placed, (leg, worker)) in placements
+                        .into_iter()
+                        .zip(legs.iter().zip(workers.iter()))
+                        .rev()
+                    {
+                        if let Err(e) = worker.revert_bid(leg.auction_id.clone(), placed).await {
+                            eprintln!("[Manager] Failed to revert cross-chain leg: {}", e);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let event = AuditEvent::CrossChainBidAccepted {
+            bidder_addr: cross_chain_bid.bidder_addr.clone(),
+            chain_ids: legs.iter().map(|leg| leg.chain_id).collect(),
+            leg_hashes: legs.iter().map(Bid::hash).collect(),
+        };
+        self.cross_chain_bids.write().await.push(cross_chain_bid);
+        self.record_audit_event(event).await;
+
+        Ok(format!(
+            "[Manager] ACK: Cross-chain bid on {} chains accepted.",
+            chain_ids.len()
+        ))
+    }
+
+    /// Withdraws a bidder's bid from the ongoing auction before its end time.
+    pub async fn cancel_bid(
+        &self,
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        cancellation: BidCancellation,
+    ) -> Result<String, AuctionError> {
         let worker_opt = {
             let workers_guard = self.workers.read().await;
             workers_guard.get(&chain_id).cloned()
         };
 
         if let Some(worker) = worker_opt {
-            worker.get_latest_tob().await
+            worker.cancel_bid(auction_id, cancellation).await
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    /// Retrieves the full internal auction state.
-    pub async fn request_auction_state(
+    /// Submits a bid commitment to a commit-reveal auction.
+    pub async fn submit_bid_commitment(
         &self,
         chain_id: ChainId,
-    ) -> Result<AuctionState, AuctionError> {
+        auction_id: AuctionId,
+        commitment: BidCommitment,
+    ) -> Result<String, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        let Some(worker) = worker_opt else {
+            return Err(AuctionError::NoAuctions);
+        };
+        let event = AuditEvent::CommitmentAccepted {
+            chain_id,
+            auction_id: auction_id.clone(),
+            bidder_addr: commitment.bidder_addr.clone(),
+            commitment_hash: commitment.hash(),
+        };
+        let ack = worker.submit_commitment(auction_id, commitment).await?;
+        self.record_audit_event(event).await;
+        Ok(ack)
+    }
+
+    /// Reveals a committed bid during the reveal phase of a commit-reveal auction.
+    pub async fn reveal_bid(
+        &self,
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bid: Bid,
+        salt: String,
+    ) -> Result<String, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        let Some(worker) = worker_opt else {
+            return Err(AuctionError::NoAuctions);
+        };
+        let event = AuditEvent::BidRevealed {
+            chain_id,
+            auction_id: auction_id.clone(),
+            bidder_addr: bid.bidder_addr.clone(),
+            bid_hash: bid.hash(),
+        };
+        let ack = worker.reveal_bid(auction_id, bid, salt).await?;
+        self.record_audit_event(event).await;
+        Ok(ack)
+    }
+
+    /// Returns the bid caps configured for a chain (unlimited if the chain is unknown).
+    pub async fn get_bid_limits(&self, chain_id: ChainId) -> BidLimits {
+        let chain_registry_guard = self.chain_registry.read().await;
+        chain_registry_guard
+            .get_chain_info(chain_id)
+            .map(|chain_info| chain_info.bid_limits)
+            .unwrap_or_default()
+    }
+
+    /// Returns the auction mode of a chain (sealed-bid if the chain is unknown).
+    pub async fn get_auction_mode(&self, chain_id: ChainId) -> AuctionMode {
+        let chain_registry_guard = self.chain_registry.read().await;
+        chain_registry_guard
+            .get_chain_info(chain_id)
+            .map(|chain_info| chain_info.auction_mode)
+            .unwrap_or_default()
+    }
+
+    /// Returns the signature scheme of a chain (secp256k1 if the chain is unknown).
+    pub async fn get_signature_scheme(&self, chain_id: ChainId) -> SignatureScheme {
+        let chain_registry_guard = self.chain_registry.read().await;
+        chain_registry_guard
+            .get_chain_info(chain_id)
+            .map(|chain_info| chain_info.signature_scheme)
+            .unwrap_or_default()
+    }
+
+    /// Retrieves the public key that bids to the current encrypted auction must be encrypted to.
+    pub async fn request_auction_public_key(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<String, AuctionError> {
         let worker_opt = {
             let workers_guard = self.workers.read().await;
             workers_guard.get(&chain_id).cloned()
         };
 
         if let Some(worker) = worker_opt {
-            worker.get_auction_state().await
+            worker.get_encryption_public_key().await
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Submits an encrypted bid to an encrypted auction. It stays sealed until the auction ends.
+    pub async fn submit_encrypted_bid(
+        &self,
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        encrypted_bid: EncryptedBid,
+    ) -> Result<String, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        let Some(worker) = worker_opt else {
+            return Err(AuctionError::NoAuctions);
+        };
+        let event = AuditEvent::EncryptedBidAccepted {
+            chain_id,
+            auction_id: auction_id.clone(),
+            bidder_addr: encrypted_bid.bidder_addr.clone(),
+            encrypted_bid_hash: encrypted_bid.hash(),
+        };
+        let ack = worker
+            .submit_encrypted_bid(auction_id, encrypted_bid)
+            .await?;
+        self.record_audit_event(event).await;
+        Ok(ack)
+    }
+
+    /// Requests the latest ToB (Top-of-Block) info for the current auction.
+    pub async fn request_latest_tob(&self, chain_id: ChainId) -> Result<Vec<Tx>, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        if let Some(worker) = worker_opt {
+            worker.get_latest_tob().await
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
+
+    /// Requests the whole ordered block segment sold by the current auction: the winning bids'
+    /// transactions first, then those of the remaining bids in the chain's `OrderingRule` until
+    /// the auction's blockspace is full. Each transaction comes with its position and source bid.
+    pub async fn request_block_ordering(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<Vec<OrderedTx>, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        if let Some(worker) = worker_opt {
+            worker.get_block_ordering().await
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Retrieves the current highest bid of an open ascending (English) auction,
+    /// or the current asking price of a descending-price (Dutch) auction.
+    pub async fn request_current_price(&self, chain_id: ChainId) -> Result<u64, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        if let Some(worker) = worker_opt {
+            worker.get_current_price().await
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Retrieves the full internal auction state.
+    pub async fn request_auction_state(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<AuctionState, AuctionError> {
+        let worker_opt = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        };
+
+        if let Some(worker) = worker_opt {
+            worker.get_auction_state().await
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Settlement
+    // ------------------------------------------------------------------------
+
+    /// Settles the auctions that have closed, and ends those that are settled.
+    ///
+    /// The package bids starting in a closed auction are resolved, and the cross-chain bids
+    /// whose legs have all been decided are settled. A closed auction is then ended, and its
+    /// result signed, unless it holds a leg of a cross-chain bid whose other auctions are still
+    /// open. It is settled again when they close.
+    async fn settle_auctions(&self) {
+        let _settlement = self.settlement_lock.lock().await;
+
+        let workers: Vec<Arc<AuctionWorker>> =
+            self.workers.read().await.values().cloned().collect();
+        let mut closed = Vec::new();
+        for worker in workers {
+            if let Ok(auction_state) = worker.get_auction_state().await {
+                if auction_state.phase == AuctionPhase::Settling {
+                    let chain_id = auction_state.auction_info.chain_id;
+                    let auction_id = auction_state.auction_info.id;
+                    self.resolve_package_bids(&worker, chain_id, &auction_id)
+                        .await;
+                    closed.push((worker, chain_id, auction_id));
+                }
+            }
+        }
+
+        let awaiting = self.settle_cross_chain_bids().await;
+        for (worker, chain_id, auction_id) in closed {
+            if !awaiting.contains(&auction_id) {
+                self.end_auction(&worker, chain_id, auction_id).await;
+            }
+        }
+    }
+
+    /// Ends a settled auction in its worker, then signs and publishes its result. This is the
+    /// only place an `AuctionResult` is signed, so every auction is signed exactly once.
+    async fn end_auction(&self, worker: &AuctionWorker, chain_id: ChainId, auction_id: AuctionId) {
+        let ended_state = match worker.finalize_auction(auction_id.clone()).await {
+            Ok(ended_state) => ended_state,
+            Err(e) => {
+                eprintln!("[Manager] Failed to end auction {}: {}", auction_id, e);
+                return;
+            }
+        };
+        self.package_awards.write().await.remove(&auction_id);
+        self.bid_sequences.lock().await.remove(&auction_id);
+
+        let result = self
+            .operator
+            .with_active_key(|operator_key| AuctionResult::new(&ended_state, operator_key))
+            .await;
+        let result_hash = match result {
+            Ok(result) => {
+                let result_hash = result.hash();
+                self.auction_results
+                    .write()
+                    .await
+                    .insert(auction_id.clone(), result);
+                if let Some(bid_tree) = ended_state.bid_tree {
+                    self.bid_trees
+                        .write()
+                        .await
+                        .insert(auction_id.clone(), bid_tree);
+                }
+                if let Some(removal_tree) = ended_state.removal_tree {
+                    self.removal_trees
+                        .write()
+                        .await
+                        .insert(auction_id.clone(), removal_tree);
+                }
+                Some(result_hash)
+            }
+            Err(e) => {
+                eprintln!("[Manager] Failed to sign auction result: {}", e);
+                None
+            }
+        };
+
+        println!(
+            "[Manager] Auction with ID {} on Chain {} has ended.",
+            auction_id, chain_id
+        );
+        let _ = self.event_sender.send(AuctionEvent::Ended {
+            chain_id,
+            auction_id: auction_id.clone(),
+        });
+        self.record_audit_event(AuditEvent::AuctionEnded {
+            chain_id,
+            auction_id,
+            result_hash,
+        })
+        .await;
+    }
+
+    // ------------------------------------------------------------------------
+    // Package bids
+    // ------------------------------------------------------------------------
+
+    /// Checks whether an auction is still queued on the chain.
+    async fn is_queued_auction(&self, chain_id: ChainId, auction_id: &AuctionId) -> bool {
+        let registry_guard = self.auction_registry.read().await;
+        registry_guard
+            .get_queued_auctions(chain_id)
+            .iter()
+            .any(|auction_info| &auction_info.id == auction_id)
+    }
+
+    /// Stages bids for a queued auction until it starts. The chain's worker validates them
+    /// against the same rules and caps as bids for its ongoing auction, and the errors of the
+    /// rejected bids are returned. `stamped` tells whether `BidService` has already stamped the
+    /// bids with their arrival sequence numbers and times (see `AuctionWorker::stage_bid`), and
+    /// `dropped` holds the receipted bids it removed from its buffer, which are logged.
+    async fn stage_bids(
+        &self,
+        chain_id: ChainId,
+        auction_id: &AuctionId,
+        bids: Vec<Bid>,
+        stamped: bool,
+        dropped: Vec<BidRemoval>,
+    ) -> Result<Vec<AuctionError>, AuctionError> {
+        let worker = {
+            let workers_guard = self.workers.read().await;
+            workers_guard.get(&chain_id).cloned()
+        }
+        .ok_or(AuctionError::NoAuctions)?;
+
+        // Checked while holding the staged bids, so that the auction cannot start in between
+        // and miss the bids
+        let mut staged_guard = self.staged_bids.write().await;
+        let auction_info = self
+            .auction_registry
+            .read()
+            .await
+            .get_queued_auctions(chain_id)
+            .into_iter()
+            .find(|auction_info| &auction_info.id == auction_id)
+            .ok_or(AuctionError::InvalidAuctionId(auction_id.clone()))?;
+        let staged = staged_guard
+            .entry(auction_id.clone())
+            .or_insert_with(|| AuctionState::new(auction_info));
+
+        for removal in dropped {
+            staged.log_dropped_submission(removal.submission_hash, removal.reason);
+        }
+
+        let mut rejected = Vec::new();
+        for bid in bids {
+            if let Err(e) = worker.stage_bid(staged, bid, stamped).await {
+                rejected.push(e);
+            }
+        }
+        Ok(rejected)
+    }
+
+    /// Drops the staged bids of a chain's auctions that are no longer queued, e.g., because
+    /// they were skipped or could not be started.
+    async fn prune_staged_bids(&self, chain_id: ChainId) {
+        let queued: HashSet<AuctionId> = self
+            .get_queued_auction_ids(chain_id)
+            .await
+            .into_iter()
+            .collect();
+        let mut staged_guard = self.staged_bids.write().await;
+        staged_guard.retain(|auction_id, staged| {
+            staged.auction_info.chain_id != chain_id || queued.contains(auction_id)
+        });
+    }
+
+    /// Hands over the package award of an auction that has just started.
+    async fn forward_package_award(&self, worker: &AuctionWorker, auction_id: &AuctionId) {
+        let award = self
+            .package_awards
+            .read()
+            .await
+            .get(auction_id)
+            .and_then(|package| package.leg_for(auction_id))
+            .cloned();
+        if let Some(leg) = award {
+            if let Err(e) = worker.assign_package_leg(auction_id.clone(), leg).await {
+                eprintln!("[Manager] Failed to assign package leg: {}", e);
+            }
+        }
+    }
+
+    /// Resolves the package bids whose first leg is in an auction that has just closed.
+    ///
+    /// Packages are considered from the highest total amount down. A package wins if none of its
+    /// auctions was already awarded to another package and its total amount beats the revenue
+    /// its legs displace: the final bids of the closed auction, and the bids staged so far for
+    /// the queued ones. The winning leg of the closed auction is assigned right away, before its
+    /// outcome is settled and signed, and the others when their auctions start.
+    async fn resolve_package_bids(
+        &self,
+        worker: &AuctionWorker,
+        chain_id: ChainId,
+        auction_id: &AuctionId,
+    ) {
+        let Some(mut packages) = self.package_bids.write().await.remove(auction_id) else {
+            return;
+        };
+
+        let Ok(ended_state) = worker.get_auction_state().await else {
+            return;
+        };
+        let queued = self
+            .auction_registry
+            .read()
+            .await
+            .get_queued_auctions(chain_id);
+
+        // Stable, so equal packages keep their submission order
+        packages.sort_by_key(|package| std::cmp::Reverse(package.total_amount()));
+
+        let mut awarded_leg = None;
+        for package in packages {
+            let mut awards_guard = self.package_awards.write().await;
+            if package
+                .legs
+                .iter()
+                .any(|leg| awards_guard.contains_key(&leg.auction_id))
+            {
+                continue;
+            }
+
+            let staged_guard = self.staged_bids.read().await;
+            let displaced: Option<u64> = package
+                .legs
+                .iter()
+                .map(|leg| {
+                    if &leg.auction_id == auction_id {
+                        return Some(allocation::displaced_revenue(
+                            &ended_state.bids,
+                            ended_state.auction_info.blockspace_size,
+                            leg.blockspace_used(),
+                        ));
+                    }
+
+                    // A queued auction that has disappeared cannot be awarded anymore
+                    let auction_info = queued.iter().find(|info| info.id == leg.auction_id)?;
+                    let staged = staged_guard
+                        .get(&leg.auction_id)
+                        .map(|staged| staged.bids.as_slice())
+                        .unwrap_or_default();
+                    Some(allocation::displaced_revenue(
+                        staged,
+                        auction_info.blockspace_size,
+                        leg.blockspace_used(),
+                    ))
+                })
+                .sum();
+
+            // Checked on submission
+            let total_amount = package.total_amount().unwrap_or_default();
+            if displaced.is_none_or(|displaced| total_amount <= displaced) {
+                println!(
+                    "[Manager] Package bid of {} starting at auction {} lost ({} vs {:?}).",
+                    package.bidder_addr, auction_id, total_amount, displaced
+                );
+                continue;
+            }
+
+            println!(
+                "[Manager] Package bid of {} starting at auction {} won ({} auctions).",
+                package.bidder_addr,
+                auction_id,
+                package.legs.len()
+            );
+            awarded_leg = package.leg_for(auction_id).cloned();
+            for leg in package.legs.iter() {
+                awards_guard.insert(leg.auction_id.clone(), package.clone());
+            }
+        }
+
+        if let Some(leg) = awarded_leg {
+            if let Err(e) = worker.assign_package_leg(auction_id.clone(), leg).await {
+                eprintln!("[Manager] Failed to assign package leg: {}", e);
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Cross-chain bids
+    // ------------------------------------------------------------------------
+
+    /// Settles the cross-chain bids whose legs have all been decided, and returns the closed
+    /// auctions that cannot be ended yet.
+    ///
+    /// A leg is lost if its auction was cancelled or replaced, or if it is not among the winners
+    /// once its auction has closed. A cross-chain bid that lost any leg has all of its legs
+    /// withdrawn, so that it wins either all of its auctions or none. Since a withdrawal
+    /// recomputes an outcome, this repeats until no remaining bid has lost a leg.
+    ///
+    /// An auction cannot be ended while a cross-chain bid links it, directly or through other
+    /// cross-chain bids, to an auction that is still open, as its outcome may change until then.
+    async fn settle_cross_chain_bids(&self) -> HashSet<AuctionId> {
+        let workers: HashMap<ChainId, Arc<AuctionWorker>> = self.workers.read().await.clone();
+        loop {
+            let mut states: HashMap<ChainId, AuctionState> = HashMap::new();
+            for (chain_id, worker) in workers.iter() {
+                if let Ok(auction_state) = worker.get_auction_state().await {
+                    states.insert(*chain_id, auction_state);
+                }
+            }
+            let leg_state = |leg: &Bid| {
+                states
+                    .get(&leg.chain_id)
+                    .filter(|auction_state| auction_state.auction_info.id == leg.auction_id)
+            };
+            let leg_lost = |leg: &Bid| match leg_state(leg) {
+                Some(auction_state) => match auction_state.phase {
+                    AuctionPhase::Bidding | AuctionPhase::Reveal => false,
+                    AuctionPhase::Settling | AuctionPhase::Ended => {
+                        let leg_hash = leg.hash();
+                        !auction_state
+                            .winners
+                            .iter()
+                            .any(|winner| winner.bid_hash == leg_hash)
+                    }
+                    AuctionPhase::Cancelled => true,
+                },
+                None => true,
+            };
+            let leg_open = |leg: &Bid| {
+                leg_state(leg).is_some_and(|auction_state| {
+                    matches!(
+                        auction_state.phase,
+                        AuctionPhase::Bidding | AuctionPhase::Reveal
+                    )
+                })
+            };
+
+            let lost: Vec<CrossChainBid> = {
+                let mut pending_guard = self.cross_chain_bids.write().await;
+                let (lost, remaining) = std::mem::take(&mut *pending_guard)
+                    .into_iter()
+                    .partition(|cross_chain_bid| cross_chain_bid.legs.iter().any(leg_lost));
+                *pending_guard = remaining;
+                lost
+            };
+
+            if lost.is_empty() {
+                // Auctions with an open leg, and every auction linked to them
+                let mut pending_guard = self.cross_chain_bids.write().await;
+                let mut awaiting: HashSet<AuctionId> = pending_guard
+                    .iter()
+                    .filter(|cross_chain_bid| cross_chain_bid.legs.iter().any(leg_open))
+                    .flat_map(|cross_chain_bid| cross_chain_bid.legs.iter())
+                    .map(|leg| leg.auction_id.clone())
+                    .collect();
+                loop {
+                    let linked: Vec<AuctionId> = pending_guard
+                        .iter()
+                        .filter(|cross_chain_bid| {
+                            cross_chain_bid
+                                .legs
+                                .iter()
+                                .any(|leg| awaiting.contains(&leg.auction_id))
+                        })
+                        .flat_map(|cross_chain_bid| cross_chain_bid.legs.iter())
+                        .map(|leg| leg.auction_id.clone())
+                        .filter(|auction_id| !awaiting.contains(auction_id))
+                        .collect();
+                    if linked.is_empty() {
+                        break;
+                    }
+                    awaiting.extend(linked);
+                }
+
+                // The others won all of their legs, whose auctions are ended next
+                pending_guard.retain(|cross_chain_bid| {
+                    let waiting = cross_chain_bid
+                        .legs
+                        .iter()
+                        .any(|leg| awaiting.contains(&leg.auction_id));
+                    if !waiting {
+                        println!(
+                            "[Manager] Cross-chain bid of {} won all {} legs.",
+                            cross_chain_bid.bidder_addr,
+                            cross_chain_bid.legs.len()
+                        );
+                    }
+                    waiting
+                });
+                return awaiting;
+            }
+
+            for cross_chain_bid in lost {
+                println!(
+                    "[Manager] Cross-chain bid of {} lost a leg; withdrawing its legs.",
+                    cross_chain_bid.bidder_addr
+                );
+                for leg in cross_chain_bid.legs.iter() {
+                    if let Some(worker) = workers.get(&leg.chain_id) {
+                        // Legs of cancelled or replaced auctions are already gone
+                        let _ = worker
+                            .withdraw_bid(leg.auction_id.clone(), &leg.hash())
+                            .await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::{timeout, Duration};
+
+    use super::*;
+    use crate::{
+        core::{
+            auction::AuctionRule,
+            domain::{AuctionWinner, ChainInfo, RemovalReason},
+        },
+        services::operator::DEFAULT_ROTATION_OVERLAP_MS,
+        utils::signing::Secp256k1Key,
+    };
+
+    /// Deterministic test keys: the seller signs with key 1, and bidders with keys 2 and up.
+    fn key(n: u64) -> Secp256k1Key {
+        Secp256k1Key::from_hex(&format!("{:064x}", n)).unwrap()
+    }
+
+    /// The operator signs with a fixed key that no seller or bidder uses.
+    fn operator() -> OperatorService {
+        OperatorService::new(key(0xff), DEFAULT_ROTATION_OVERLAP_MS)
+    }
+
+    /// Creates a manager with the given chains registered, each accepting auctions from the seller
+    /// and selecting winners with `auction_rule` if any. The workers of the chains do not run
+    /// their loop, so that auctions only close when the test calls `end_auctions`.
+    async fn setup(
+        chain_ids: &[ChainId],
+        auction_rule: Option<Arc<dyn AuctionRule>>,
+    ) -> (RegistryService, AuctionManager) {
+        let registry_service = {
+            let (auction_registry, chain_registry) = RegistryService::create_registry().await;
+            RegistryService::new(auction_registry, chain_registry)
+        };
+        let manager = AuctionManager::new(&registry_service, operator()).await;
+        for &chain_id in chain_ids {
+            registry_service
+                .register_chain(
+                    chain_id,
+                    ChainInfo {
+                        registered_sellers: vec![key(1).address()],
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            if let Some(auction_rule) = &auction_rule {
+                registry_service
+                    .register_auction_rule(chain_id, auction_rule.clone())
+                    .await
+                    .unwrap();
+            }
+            manager.add_worker(chain_id).await.unwrap();
+        }
+        (registry_service, manager)
+    }
+
+    /// Closes the ongoing auctions of the given chains, whose end time has passed, and waits
+    /// until the manager has ended all of them.
+    async fn end_auctions(manager: &AuctionManager, chain_ids: &[ChainId]) {
+        let mut events = manager.subscribe_events();
+        let mut closing = HashSet::new();
+        for chain_id in chain_ids {
+            let worker = manager.workers.read().await.get(chain_id).cloned().unwrap();
+            closing.insert(worker.get_auction_state().await.unwrap().auction_info.id);
+            worker.process_auction().await.unwrap();
+        }
+        while !closing.is_empty() {
+            let event = timeout(Duration::from_secs(5), events.recv()).await;
+            if let AuctionEvent::Ended { auction_id, .. } = event.unwrap().unwrap() {
+                closing.remove(&auction_id);
+            }
+        }
+    }
+
+    /// Creates an auction signed by the seller.
+    fn auction_info(
+        chain_id: ChainId,
+        block_number: u64,
+        blockspace_size: u64,
+        start_time: u64,
+        end_time: u64,
+    ) -> AuctionInfo {
+        let seller = key(1);
+        let info = AuctionInfo::new(
+            chain_id,
+            block_number,
+            seller.address(),
+            blockspace_size,
+            start_time,
+            end_time,
+            String::new(),
+        );
+        let seller_signature = seller.sign(&info.signing_hash()).unwrap();
+        info.with_seller_signature(seller_signature)
+    }
+
+    /// Signs a bid with the bidder's key, e.g., after changing its fields.
+    fn signed(mut bid: Bid, bidder: &Secp256k1Key) -> Bid {
+        bid.bidder_signature = bidder.sign(&bid.signing_hash()).unwrap();
+        bid
+    }
+
+    /// Creates a bid on chain 1 signed by the bidder.
+    fn bid(auction_id: &AuctionId, bidder: &Secp256k1Key, bid_amount: u64) -> Bid {
+        let bid = Bid {
+            chain_id: 1,
+            auction_id: auction_id.clone(),
+            bidder_addr: bidder.address(),
+            bid_amount,
+            blockspace_size: 500,
+            ..Default::default()
+        };
+        signed(bid, bidder)
+    }
+
+    #[tokio::test]
+    async fn test_package_bid_wins_consecutive_blocks_over_single_bids() {
+        let (registry_service, manager) = setup(&[1], None).await;
+        let now = current_unix_ms();
+        let [first, second, third] = [
+            (100, now - 2000, now - 1000),
+            (101, now + 60_000, now + 61_000),
+            (102, now + 62_000, now + 63_000),
+        ]
+        .map(|(block_number, start_time, end_time)| {
+            auction_info(1, block_number, 500, start_time, end_time)
+        });
+        for info in [&first, &second, &third] {
+            registry_service
+                .submit_auction_info(info.clone())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(manager.start_next_auction(1).await, Some(first.id.clone()));
+
+        // Single-block bids: the one for the queued auction is staged
+        manager
+            .submit_bid(1, first.id.clone(), bid(&first.id, &key(3), 1000))
+            .await
+            .unwrap();
+        manager
+            .submit_bid(1, second.id.clone(), bid(&second.id, &key(4), 700))
+            .await
+            .unwrap();
+
+        // Staged bids are validated as bids for the ongoing auction are
+        let mut forged = bid(&second.id, &key(5), 2000);
+        forged.bidder_addr = key(4).address();
+        let result = manager.submit_bid(1, second.id.clone(), forged).await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+        let result = manager
+            .submit_bid(1, second.id.clone(), bid(&second.id, &key(4), 800))
+            .await;
+        assert!(matches!(result, Err(AuctionError::StaleBidNonce { .. })));
+        assert_eq!(manager.staged_bids.read().await[&second.id].bids.len(), 1);
+
+        let package_key = key(2);
+        let package_addr = package_key.address();
+        let package = |auction_ids: [&AuctionId; 2]| {
+            let mut package = PackageBid {
+                chain_id: 1,
+                bidder_addr: package_addr.clone(),
+                legs: auction_ids
+                    .map(|auction_id| bid(auction_id, &package_key, 900))
+                    .to_vec(),
+                ..Default::default()
+            };
+            package.bidder_signature = package_key.sign(&package.signing_hash()).unwrap();
+            package
+        };
+
+        // Legs must cover consecutive blocks
+        let result = manager
+            .submit_package_bid(package([&first.id, &third.id]))
+            .await;
+        assert!(matches!(result, Err(AuctionError::InvalidPackageBid(_))));
+
+        // The signature must cover the legs as submitted
+        let mut forged = package([&first.id, &second.id]);
+        forged.legs[1].bid_amount = 100;
+        let result = manager.submit_package_bid(forged).await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+
+        // 900 + 900 beats the 1000 + 700 of the single-block bids, though not block by block
+        manager
+            .submit_package_bid(package([&first.id, &second.id]))
+            .await
+            .unwrap();
+
+        end_auctions(&manager, &[1]).await;
+
+        let state = manager.request_auction_state(1).await.unwrap();
+        assert!(state.is_ended);
+        assert_eq!(state.winners.len(), 1);
+        assert_eq!(state.winners[0].bidder_addr, package_addr);
+        assert_eq!(state.clearing_price, 900);
+
+        let awards = manager.package_awards.read().await;
+        let award = awards
+            .get(&second.id)
+            .and_then(|package| package.leg_for(&second.id));
+        assert_eq!(award.unwrap().bid_amount, 900);
+        assert!(!awards.contains_key(&first.id));
+    }
+
+    #[tokio::test]
+    async fn test_cross_chain_bid_wins_all_legs_or_none() {
+        let (registry_service, manager) = setup(&[1, 2], None).await;
+        let now = current_unix_ms();
+        let mut auction_ids = Vec::new();
+        for chain_id in [1, 2] {
+            let info = auction_info(chain_id, 100, 500, now - 2000, now - 1000);
+            auction_ids.push(info.id.clone());
+            registry_service.submit_auction_info(info).await.unwrap();
+            manager.start_next_auction(chain_id).await.unwrap();
+        }
+
+        let arbitrageur_key = key(2);
+        let arbitrageur_addr = arbitrageur_key.address();
+        let leg = |chain_id: ChainId, bid_amount: u64| Bid {
+            chain_id,
+            ..bid(
+                &auction_ids[chain_id as usize - 1],
+                &arbitrageur_key,
+                bid_amount,
+            )
+        };
+        let mut cross_chain_bid = CrossChainBid {
+            bidder_addr: arbitrageur_addr.clone(),
+            legs: vec![leg(1, 1000), leg(2, 500)],
+            ..Default::default()
+        };
+        cross_chain_bid.bidder_signature = arbitrageur_key
+            .sign(&cross_chain_bid.signing_hash())
+            .unwrap();
+        manager
+            .submit_cross_chain_bid(cross_chain_bid)
+            .await
+            .unwrap();
+
+        // The leg on chain 1 would win, but the one on chain 2 is outbid
+        let single_bids = [(1, key(3), 800), (2, key(4), 900)];
+        for (chain_id, bidder, bid_amount) in &single_bids {
+            let single_bid = Bid {
+                chain_id: *chain_id,
+                ..bid(&auction_ids[*chain_id as usize - 1], bidder, *bid_amount)
+            };
+            let single_bid = signed(single_bid, bidder);
+            manager
+                .submit_bid(*chain_id, single_bid.auction_id.clone(), single_bid)
+                .await
+                .unwrap();
+        }
+
+        end_auctions(&manager, &[1, 2]).await;
+
+        for (chain_id, bidder, bid_amount) in single_bids {
+            let state = manager.request_auction_state(chain_id).await.unwrap();
+            assert!(state.is_ended);
+            assert_eq!(state.winners.len(), 1);
+            assert_eq!(state.winners[0].bidder_addr, bidder.address());
+            assert_eq!(state.clearing_price, bid_amount);
+
+            // The result is signed once, after the losing legs were withdrawn
+            let result = manager
+                .get_auction_result(&state.auction_info.id)
+                .await
+                .unwrap();
+            assert_eq!(result.winners, state.winners);
+        }
+        assert!(manager.cross_chain_bids.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_closed_auction_waits_for_cross_chain_legs_to_be_decided() {
+        let (registry_service, manager) = setup(&[1, 2], None).await;
+        let now = current_unix_ms();
+        let mut auction_ids = Vec::new();
+        for chain_id in [1, 2] {
+            let info = auction_info(chain_id, 100, 500, now - 2000, now - 1000);
+            auction_ids.push(info.id.clone());
+            registry_service.submit_auction_info(info).await.unwrap();
+            manager.start_next_auction(chain_id).await.unwrap();
+        }
+
+        let arbitrageur_key = key(2);
+        let leg = |chain_id: ChainId| Bid {
+            chain_id,
+            ..bid(&auction_ids[chain_id as usize - 1], &arbitrageur_key, 1000)
+        };
+        let mut cross_chain_bid = CrossChainBid {
+            bidder_addr: arbitrageur_key.address(),
+            legs: vec![leg(1), leg(2)],
+            ..Default::default()
+        };
+        cross_chain_bid.bidder_signature = arbitrageur_key
+            .sign(&cross_chain_bid.signing_hash())
+            .unwrap();
+        manager
+            .submit_cross_chain_bid(cross_chain_bid)
+            .await
+            .unwrap();
+        let single_bid = bid(&auction_ids[0], &key(3), 800);
+        manager
+            .submit_bid(1, auction_ids[0].clone(), single_bid)
+            .await
+            .unwrap();
+
+        // The auction on chain 1 closes, but its leg waits on chain 2 and nothing is signed
+        let worker = manager.workers.read().await.get(&1).cloned().unwrap();
+        worker.process_auction().await.unwrap();
+        timeout(Duration::from_secs(5), async {
+            while manager.get_ongoing_auction_id(1).await.is_some() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        let state = manager.request_auction_state(1).await.unwrap();
+        assert_eq!(state.phase, AuctionPhase::Settling);
+        assert!(manager.get_auction_result(&auction_ids[0]).await.is_none());
+        assert!(registry_service
+            .submit_auction_info(auction_info(1, 101, 500, now - 500, now + 60_000))
+            .await
+            .is_ok());
+        assert_eq!(manager.start_next_auction(1).await, None);
+
+        // Cancelling the auction on chain 2 loses the other leg, and chain 1 ends without it
+        let mut events = manager.subscribe_events();
+        let seller = key(1);
+        let mut cancellation = AuctionCancellation {
+            chain_id: 2,
+            auction_id: auction_ids[1].clone(),
+            seller_address: seller.address(),
+            seller_signature: String::new(),
+        };
+        cancellation.seller_signature = seller.sign(&cancellation.signing_hash()).unwrap();
+        manager.cancel_auction(cancellation).await.unwrap();
+        loop {
+            let event = timeout(Duration::from_secs(5), events.recv()).await;
+            if let AuctionEvent::Ended { auction_id, .. } = event.unwrap().unwrap() {
+                assert_eq!(auction_id, auction_ids[0]);
+                break;
+            }
+        }
+
+        let state = manager.request_auction_state(1).await.unwrap();
+        assert_eq!(state.phase, AuctionPhase::Ended);
+        assert_eq!(state.winners.len(), 1);
+        assert_eq!(state.winners[0].bidder_addr, key(3).address());
+        let result = manager.get_auction_result(&auction_ids[0]).await.unwrap();
+        assert_eq!(result.winners, state.winners);
+        assert!(manager.cross_chain_bids.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_cross_chain_leg_restores_replaced_bid() {
+        let (registry_service, manager) = setup(&[1, 2], None).await;
+        let now = current_unix_ms();
+        let mut auction_ids = Vec::new();
+        for chain_id in [1, 2] {
+            let info = auction_info(chain_id, 100, 500, now - 2000, now - 1000);
+            auction_ids.push(info.id.clone());
+            registry_service.submit_auction_info(info).await.unwrap();
+            manager.start_next_auction(chain_id).await.unwrap();
+        }
+
+        // The arbitrageur's earlier bids: nonce 1 on chain 1, and nonce 5 on chain 2
+        let arbitrageur_key = key(2);
+        let earlier_bid = |chain_id: ChainId, nonce: u64| {
+            let earlier_bid = Bid {
+                chain_id,
+                nonce,
+                ..bid(&auction_ids[chain_id as usize - 1], &arbitrageur_key, 600)
+            };
+            signed(earlier_bid, &arbitrageur_key)
+        };
+        let earlier_bids = [earlier_bid(1, 1), earlier_bid(2, 5)];
+        for earlier_bid in earlier_bids.iter() {
+            manager
+                .submit_bid(
+                    earlier_bid.chain_id,
+                    earlier_bid.auction_id.clone(),
+                    earlier_bid.clone(),
+                )
+                .await
+                .unwrap();
+        }
+
+        // The leg on chain 1 replaces the earlier bid, and the one on chain 2 has a stale nonce
+        let leg = |chain_id: ChainId| Bid {
+            chain_id,
+            nonce: 2,
+            ..bid(&auction_ids[chain_id as usize - 1], &arbitrageur_key, 1000)
+        };
+        let mut cross_chain_bid = CrossChainBid {
+            bidder_addr: arbitrageur_key.address(),
+            legs: vec![leg(1), leg(2)],
+            ..Default::default()
+        };
+        cross_chain_bid.bidder_signature = arbitrageur_key
+            .sign(&cross_chain_bid.signing_hash())
+            .unwrap();
+        let result = manager.submit_cross_chain_bid(cross_chain_bid).await;
+        assert!(matches!(
+            result,
+            Err(AuctionError::StaleBidNonce {
+                nonce: 2,
+                current_nonce: 5
+            })
+        ));
+
+        let state = manager.request_auction_state(1).await.unwrap();
+        let bid_hashes: Vec<String> = state.bids.iter().map(|b| b.hash()).collect();
+        assert_eq!(bid_hashes, vec![earlier_bids[0].hash()]);
+        assert_eq!(state.bidder_nonces[&arbitrageur_key.address()], 1);
+        assert_eq!(state.winners[0].bid_hash, earlier_bids[0].hash());
+        assert!(manager.cross_chain_bids.read().await.is_empty());
+    }
+
+    /// Fills the blockspace greedily in bid order and charges every winner the lowest winning bid.
+    struct UniformPriceRule;
+
+    impl AuctionRule for UniformPriceRule {
+        fn select_winners(
+            &self,
+            bids: &[Bid],
+            capacity: u64,
+            reserve_price: u64,
+        ) -> Vec<AuctionWinner> {
+            let mut remaining = capacity;
+            let winning_bids: Vec<&Bid> = bids
+                .iter()
+                .filter(|bid| {
+                    let fits = bid.blockspace_used() <= remaining;
+                    if fits {
+                        remaining -= bid.blockspace_used();
+                    }
+                    fits
+                })
+                .collect();
+            let price = winning_bids
+                .iter()
+                .map(|bid| bid.effective_value())
+                .min()
+                .unwrap_or(0)
+                .max(reserve_price);
+
+            winning_bids
+                .into_iter()
+                .map(|bid| AuctionWinner {
+                    bidder_addr: bid.bidder_addr.clone(),
+                    bid_hash: bid.hash(),
+                    bid_amount: bid.effective_value(),
+                    allocated_size: bid.blockspace_used(),
+                    price,
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_uses_auction_rule_registered_for_chain() {
+        let (registry_service, manager) = setup(&[1], Some(Arc::new(UniformPriceRule))).await;
+        let now = current_unix_ms();
+        let info = auction_info(1, 100, 500, now - 2000, now - 1000);
+        registry_service
+            .submit_auction_info(info.clone())
+            .await
+            .unwrap();
+
+        manager.start_next_auction(1).await.unwrap();
+        let bids = [(key(3), 1000, 300), (key(4), 900, 300), (key(5), 600, 200)];
+        for (bidder, bid_amount, blockspace_size) in &bids {
+            let bid = Bid {
+                blockspace_size: *blockspace_size,
+                ..bid(&info.id, bidder, *bid_amount)
+            };
+            let bid = signed(bid, bidder);
+            manager.submit_bid(1, info.id.clone(), bid).await.unwrap();
+        }
+
+        end_auctions(&manager, &[1]).await;
+
+        let state = manager.request_auction_state(1).await.unwrap();
+        let winners: Vec<(String, u64)> = state
+            .winners
+            .iter()
+            .map(|winner| (winner.bidder_addr.clone(), winner.price))
+            .collect();
+        assert_eq!(
+            winners,
+            vec![(key(3).address(), 600), (key(5).address(), 600)]
+        );
+        assert_eq!(state.clearing_price, 1200);
+    }
+
+    #[tokio::test]
+    async fn test_seller_cancels_queued_and_running_auctions() {
+        let (registry_service, manager) = setup(&[1], None).await;
+        let now = current_unix_ms();
+        let [running, queued] = [
+            (100, now - 1000, now + 60_000),
+            (101, now + 62_000, now + 63_000),
+        ]
+        .map(|(block_number, start_time, end_time)| {
+            auction_info(1, block_number, 500, start_time, end_time)
+        });
+        for info in [&running, &queued] {
+            registry_service
+                .submit_auction_info(info.clone())
+                .await
+                .unwrap();
+        }
+
+        let mut events = manager.subscribe_events();
+        assert_eq!(
+            manager.start_next_auction(1).await,
+            Some(running.id.clone())
+        );
+        manager
+            .submit_bid(1, running.id.clone(), bid(&running.id, &key(3), 1000))
+            .await
+            .unwrap();
+        manager
+            .submit_bid(1, queued.id.clone(), bid(&queued.id, &key(4), 700))
+            .await
+            .unwrap();
+
+        let cancellation = |auction_id: &AuctionId, seller: &Secp256k1Key| {
+            let mut cancellation = AuctionCancellation {
+                chain_id: 1,
+                auction_id: auction_id.clone(),
+                seller_address: seller.address(),
+                seller_signature: String::new(),
+            };
+            cancellation.seller_signature = seller.sign(&cancellation.signing_hash()).unwrap();
+            cancellation
+        };
+
+        // Only the seller of an auction may cancel it
+        let result = manager
+            .cancel_auction(cancellation(&queued.id, &key(3)))
+            .await;
+        assert!(matches!(result, Err(AuctionError::NotAuctionSeller(_))));
+
+        let mut forged = cancellation(&queued.id, &key(3));
+        forged.seller_address = key(1).address();
+        let result = manager.cancel_auction(forged).await;
+        assert!(matches!(result, Err(AuctionError::InvalidSellerSignature)));
+
+        manager
+            .cancel_auction(cancellation(&queued.id, &key(1)))
+            .await
+            .unwrap();
+        assert!(manager.get_queued_auction_ids(1).await.is_empty());
+        assert!(!manager.staged_bids.read().await.contains_key(&queued.id));
+        assert_eq!(
+            events.recv().await.unwrap(),
+            AuctionEvent::Cancelled {
+                chain_id: 1,
+                auction_id: queued.id.clone(),
+            }
+        );
+
+        manager
+            .cancel_auction(cancellation(&running.id, &key(1)))
+            .await
+            .unwrap();
+        assert_eq!(manager.get_ongoing_auction_id(1).await, None);
+        let state = manager.request_auction_state(1).await.unwrap();
+        assert_eq!(state.phase, AuctionPhase::Cancelled);
+        assert!(state.winners.is_empty());
+        assert_eq!(
+            events.recv().await.unwrap(),
+            AuctionEvent::Cancelled {
+                chain_id: 1,
+                auction_id: running.id.clone(),
+            }
+        );
+
+        // A cancelled auction cannot be cancelled again
+        let result = manager
+            .cancel_auction(cancellation(&running.id, &key(1)))
+            .await;
+        assert!(matches!(result, Err(AuctionError::InvalidAuctionId(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ended_auction_has_signed_result() {
+        let (registry_service, manager) = setup(&[1], None).await;
+        let now = current_unix_ms();
+        let info = auction_info(1, 100, 1000, now - 2000, now - 1000);
+        registry_service
+            .submit_auction_info(info.clone())
+            .await
+            .unwrap();
+
+        manager.start_next_auction(1).await;
+        // The first bid of key 3 is replaced by its second
+        let replaced_bid = bid(&info.id, &key(3), 400);
+        manager
+            .submit_bid(1, info.id.clone(), replaced_bid.clone())
+            .await
+            .unwrap();
+        for (bidder, bid_amount) in [(key(3), 1000), (key(4), 700)] {
+            let mut b = bid(&info.id, &bidder, bid_amount);
+            b.nonce = 1;
+            b.tx_list = vec![Tx {
+                tx_data: format!("{}Tx", bidder.address()),
+                gas_limit: 500,
+            }];
+            let b = signed(b, &bidder);
+            manager.submit_bid(1, info.id.clone(), b).await.unwrap();
+        }
+        assert!(manager.get_auction_result(&info.id).await.is_none());
+
+        end_auctions(&manager, &[1]).await;
+
+        let result = manager.get_auction_result(&info.id).await.unwrap();
+        assert_eq!(result.winners.len(), 2);
+        assert_eq!(result.bid_count, 2);
+        assert_eq!(result.end_time, info.end_time);
+
+        let operator_address = manager.get_operator_address().await;
+        assert!(result.verify(&operator_address));
+        assert!(!result.verify("0x0000000000000000000000000000000000000000"));
+
+        let mut tampered = result.clone();
+        tampered.clearing_price += 1;
+        assert!(!tampered.verify(&operator_address));
+
+        // Every bid can be proven to be in the published commitment
+        let ended_state = manager.request_auction_state(1).await.unwrap();
+        for b in &ended_state.bids {
+            let proof = manager
+                .get_bid_inclusion_proof(&info.id, &b.hash())
+                .await
+                .unwrap();
+            assert!(result.verify_bid_inclusion(&b.hash(), &proof));
+        }
+
+        // So can the replaced bid, along with its removal
+        let proof = manager
+            .get_bid_inclusion_proof(&info.id, &replaced_bid.hash())
+            .await
+            .unwrap();
+        assert!(result.verify_bid_inclusion(&replaced_bid.hash(), &proof));
+        let removal = BidRemoval {
+            submission_hash: replaced_bid.hash(),
+            reason: RemovalReason::Replaced,
+        };
+        let proof = manager
+            .get_bid_removal_proof(&info.id, &removal)
+            .await
+            .unwrap();
+        assert!(result.verify_bid_removal(&removal, &proof));
+        let evicted = BidRemoval {
+            reason: RemovalReason::Evicted,
+            ..removal
+        };
+        assert!(!result.verify_bid_removal(&evicted, &proof));
+
+        let missing_bid = bid(&info.id, &key(5), 500);
+        assert!(matches!(
+            manager
+                .get_bid_inclusion_proof(&info.id, &missing_bid.hash())
+                .await,
+            Err(AuctionError::BidNotFound(_))
+        ));
+    }
 }
@@ -1,5 +1,8 @@
+pub mod allocation;
 pub mod manager;
+pub mod rule;
 pub mod worker;
 
 pub use manager::AuctionManager;
+pub use rule::{AuctionRule, RevenueMaximizingRule};
 pub use worker::AuctionWorker;
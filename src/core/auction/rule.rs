@@ -0,0 +1,72 @@
+use crate::core::{
+    auction::allocation,
+    domain::{AuctionWinner, Bid, PricingRule},
+};
+
+/// A winner-selection mechanism for sealed-bid auctions (including commit-reveal and encrypted
+/// ones). A rule can be registered per chain in `ChainRegistry`; chains without one use
+/// `RevenueMaximizingRule` with their `PricingRule`.
+pub trait AuctionRule: Send + Sync {
+    /// Selects the winning bids and the price each of them pays.
+    ///
+    /// `bids` are sorted best first by the auction's `TieBreakRule`. `capacity` is the blockspace
+    /// left after the leg of a winning package bid, if any, and the winners must fit into it.
+    /// No winner should be charged less than `reserve_price`. The worker drops winners that
+    /// break these constraints or do not refer to a distinct bid in `bids`.
+    fn select_winners(&self, bids: &[Bid], capacity: u64, reserve_price: u64)
+        -> Vec<AuctionWinner>;
+}
+
+/// The default rule: allocates the blockspace to the set of bids with the highest total
+/// effective value, and charges the winners according to a `PricingRule`.
+pub struct RevenueMaximizingRule {
+    pricing_rule: PricingRule,
+}
+
+impl RevenueMaximizingRule {
+    /// Creates a new `RevenueMaximizingRule` charging winners under the given `PricingRule`.
+    pub fn new(pricing_rule: PricingRule) -> Self {
+        RevenueMaximizingRule { pricing_rule }
+    }
+}
+
+impl AuctionRule for RevenueMaximizingRule {
+    fn select_winners(
+        &self,
+        bids: &[Bid],
+        capacity: u64,
+        reserve_price: u64,
+    ) -> Vec<AuctionWinner> {
+        let selected = allocation::allocate_blockspace(bids, capacity);
+        let total_revenue = selected.iter().fold(0u64, |total, &index| {
+            total.saturating_add(bids[index].effective_value())
+        });
+
+        selected
+            .into_iter()
+            .map(|index| {
+                let bid = &bids[index];
+                let price = match self.pricing_rule {
+                    PricingRule::FirstPrice => bid.effective_value(),
+                    // The revenue the others could have earned without this bid,
+                    // minus what they earn alongside it. Once the allocation search thins its
+                    // frontier both are approximate, and their difference can exceed the bid,
+                    // so the price is capped at it.
+                    PricingRule::SecondPrice => {
+                        allocation::max_revenue(bids, capacity, Some(index))
+                            .saturating_sub(total_revenue - bid.effective_value())
+                            .min(bid.effective_value())
+                    }
+                }
+                .max(reserve_price);
+                AuctionWinner {
+                    bidder_addr: bid.bidder_addr.clone(),
+                    bid_hash: bid.hash(),
+                    bid_amount: bid.effective_value(),
+                    allocated_size: bid.blockspace_used(),
+                    price,
+                }
+            })
+            .collect()
+    }
+}
@@ -1,15 +1,30 @@
-use std::sync::Arc;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use tokio::{
-    sync::{mpsc::Sender, RwLock},
+    sync::{mpsc::Sender, Mutex, RwLock},
     time::{sleep, Duration},
 };
 
 use crate::{
-    core::domain::{
-        AuctionId, AuctionInfo, AuctionState, Bid, ChainId, Tx, WorkerMessage, WorkerMessageType,
+    core::{
+        auction::{AuctionRule, RevenueMaximizingRule},
+        domain::{
+            AuctionId, AuctionInfo, AuctionMode, AuctionPhase, AuctionState, AuctionWinner, Bid,
+            BidCancellation, BidCommitment, BidLimits, BidRemoval, ChainId, ChainInfo,
+            EncryptedBid, OrderedTx, OrderingRule, RemovalReason, SignatureScheme, SoftCloseRule,
+            Tx, WorkerMessage, WorkerMessageType,
+        },
+    },
+    utils::{
+        encryption::AuctionKeypair,
+        errors::AuctionError,
+        helpers::current_unix_ms,
+        types::{ArcMutexHashMap, ArcRwLock},
     },
-    utils::{errors::AuctionError, helpers::current_unix_ms, types::ArcRwLock},
 };
 
 /// `AuctionWorker` manages the state (`AuctionState`) for a specific `ChainId`.
@@ -20,24 +35,98 @@ pub struct AuctionWorker {
     /// The chain that this worker is responsible for
     chain_id: ChainId,
 
+    /// Selects the winners of sealed-bid auctions and the prices they pay
+    auction_rule: Arc<dyn AuctionRule>,
+
+    /// Extends the end time when a new leading bid arrives shortly before it (if configured)
+    soft_close: Option<SoftCloseRule>,
+
+    /// Determines whether bids are sealed or openly ascending
+    auction_mode: AuctionMode,
+
+    /// Caps on the number of bids kept in the auction state
+    bid_limits: BidLimits,
+
+    /// Orders the bids that fill the blockspace left after the winners
+    ordering_rule: OrderingRule,
+
+    /// Checks the signatures of bids, commitments, and cancellations
+    signature_scheme: SignatureScheme,
+
     /// Tracks the current `AuctionState`. If there is no active auction, it is `None`.
     state: ArcRwLock<Option<AuctionState>>,
 
+    /// The keypair of the current encrypted auction. The secret key is dropped once the
+    /// auction's bids have been decrypted.
+    keypair: ArcRwLock<Option<AuctionKeypair>>,
+
+    /// Tracks the next arrival sequence number for each auction, shared with `BidService` so
+    /// that bids stamped by either never share a number
+    bid_sequences: ArcMutexHashMap<AuctionId, u64>,
+
     /// Sender for notifying the manager when an auction ends or is processing
     result_sender: Sender<WorkerMessage>,
 }
 
+/// What accepting a bid changed in the auction state, so that the bid can be reverted.
+#[derive(Debug, Clone)]
+pub struct BidPlacement {
+    /// Whether the bid took the lead
+    pub is_leading: bool,
+
+    /// Hash of the accepted bid
+    pub bid_hash: String,
+
+    /// Address of the bidder
+    pub bidder_addr: String,
+
+    /// The bidder's earlier bid, which the accepted bid replaced
+    pub replaced: Option<Bid>,
+
+    /// The bid evicted to keep the auction within its bid cap
+    pub evicted: Option<Bid>,
+
+    /// The bidder's nonce before the bid was accepted
+    pub previous_nonce: Option<u64>,
+}
+
 impl AuctionWorker {
-    /// Creates a new `AuctionWorker`.
+    /// Creates a new `AuctionWorker` using the auction settings of the given `ChainInfo`.
+    /// Winners are selected by a `RevenueMaximizingRule` with the chain's `PricingRule`.
     /// Initially, there is no active auction, so the `state` is `None`.
-    pub fn new(chain_id: ChainId, result_sender: Sender<WorkerMessage>) -> Self {
+    pub fn new(
+        chain_id: ChainId,
+        chain_info: &ChainInfo,
+        result_sender: Sender<WorkerMessage>,
+    ) -> Self {
         AuctionWorker {
             chain_id,
+            auction_rule: Arc::new(RevenueMaximizingRule::new(chain_info.pricing_rule)),
+            soft_close: chain_info.soft_close,
+            auction_mode: chain_info.auction_mode,
+            bid_limits: chain_info.bid_limits,
+            ordering_rule: chain_info.ordering_rule,
+            signature_scheme: chain_info.signature_scheme,
             state: Arc::new(RwLock::new(None)),
+            keypair: Arc::new(RwLock::new(None)),
+            bid_sequences: Arc::new(Mutex::new(HashMap::new())),
             result_sender,
         }
     }
 
+    /// Shares the arrival sequence numbers of the auctions with others that stamp bids, e.g.,
+    /// the `AuctionManager`'s.
+    pub fn with_bid_sequences(mut self, bid_sequences: ArcMutexHashMap<AuctionId, u64>) -> Self {
+        self.bid_sequences = bid_sequences;
+        self
+    }
+
+    /// Replaces the rule that selects the winners of sealed-bid auctions.
+    pub fn with_auction_rule(mut self, auction_rule: Arc<dyn AuctionRule>) -> Self {
+        self.auction_rule = auction_rule;
+        self
+    }
+
     /// Main worker loop. If there is an active auction, it periodically checks
     /// whether it has ended, sorts bids, determines the highest bidder, etc.
     pub async fn run(&self) {
@@ -60,9 +149,39 @@ impl AuctionWorker {
         &self,
         auction_id: AuctionId,
         info: AuctionInfo,
+    ) -> Result<(), AuctionError> {
+        self.start_staged_auction(auction_id, AuctionState::new(info))
+            .await
+    }
+
+    /// Starts an auction from the state its bids were staged in while it was queued (see
+    /// `stage_bid`), so that the staged bids and their log carry over.
+    pub async fn start_staged_auction(
+        &self,
+        auction_id: AuctionId,
+        staged: AuctionState,
     ) -> Result<(), AuctionError> {
         let mut guard = self.state.write().await;
-        let new_state = AuctionState::new(info);
+        // The winners of a closed auction are held until the manager has settled it
+        if guard
+            .as_ref()
+            .is_some_and(|auction_state| auction_state.phase == AuctionPhase::Settling)
+        {
+            return Err(AuctionError::AuctionSettling);
+        }
+        let mut new_state = staged;
+
+        // Encrypted auctions get a fresh keypair, so that bids are never readable across auctions
+        let keypair = match self.auction_mode {
+            AuctionMode::Encrypted => {
+                let keypair = AuctionKeypair::generate();
+                new_state.encryption_public_key = Some(keypair.public_key());
+                Some(keypair)
+            }
+            _ => None,
+        };
+        *self.keypair.write().await = keypair;
+
         println!(
             "[Worker {}] Starting new auction with ID: {}",
             self.chain_id, auction_id
@@ -72,26 +191,229 @@ impl AuctionWorker {
     }
 
     /// Submits a bid. Returns an error if the auction is already ended or does not exist.
+    /// The bid is stamped with its arrival sequence number and time here, so whatever the client
+    /// set in `sequence` and `arrival_time` is ignored.
     pub async fn submit_bid(
         &self,
         auction_id: AuctionId,
         bid: Bid,
     ) -> Result<String, AuctionError> {
+        self.submit_single_bid(auction_id.clone(), bid, true)
+            .await?;
+        Ok(format!(
+            "[Worker {}] ACK: Auction {} bid accepted.",
+            self.chain_id, auction_id
+        ))
+    }
+
+    /// Submits the leg of a `CrossChainBid`. The leg is covered by the signature of the whole
+    /// cross-chain bid, which the manager checks, so it needs no signature of its own.
+    /// Returns what placing the leg changed, so that it can be undone with `revert_bid`.
+    pub async fn submit_cross_chain_leg(
+        &self,
+        auction_id: AuctionId,
+        leg: Bid,
+    ) -> Result<BidPlacement, AuctionError> {
+        self.submit_single_bid(auction_id, leg, false).await
+    }
+
+    async fn submit_single_bid(
+        &self,
+        auction_id: AuctionId,
+        mut bid: Bid,
+        check_signature: bool,
+    ) -> Result<BidPlacement, AuctionError> {
         let mut guard = self.state.write().await;
         if let Some(ref mut auction_state) = *guard {
             if auction_state.is_ended {
                 return Err(AuctionError::AuctionEnded);
             }
 
+            // Commit-reveal auctions only accept bids through `reveal_bid`,
+            // and encrypted auctions only through `submit_encrypted_bid`
+            match self.auction_mode {
+                AuctionMode::CommitReveal { .. } => return Err(AuctionError::CommitmentRequired),
+                AuctionMode::Encrypted => return Err(AuctionError::EncryptedBidRequired),
+                _ => {}
+            }
+
+            // Potential place to check if the provided auction_id matches the current state's ID
+            if auction_state.auction_info.id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
+            }
+
+            let mut sequences_guard = self.bid_sequences.lock().await;
+            let next_sequence = stamp_arrival(&mut sequences_guard, &mut bid);
+            let arrival_time = bid.arrival_time;
+            let placement = match check_signature {
+                true => self.accept_bid(auction_state, bid)?,
+                false => self.admit_bid(auction_state, bid)?,
+            };
+            *next_sequence += 1;
+            drop(sequences_guard);
+            self.after_bids_accepted(auction_state, placement.is_leading.then_some(arrival_time))
+                .await;
+            Ok(placement)
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Submits a batch of bids flushed by `BidService`, which stamped them with their arrival
+    /// sequence numbers and times when it stored them. Invalid bids are dropped from the batch.
+    ///
+    /// `BidService` issued a receipt for each bid, so the rejected ones are logged as removals,
+    /// as are the receipted bids it removed from its buffer since the last flush (`dropped`).
+    /// This holds until the auction has ended and its logs are committed.
+    pub async fn submit_bid_batch(
+        &self,
+        auction_id: AuctionId,
+        bids: Vec<Bid>,
+        dropped: Vec<BidRemoval>,
+    ) -> Result<(), AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
             // Potential place to check if the provided auction_id matches the current state's ID
             if auction_state.auction_info.id != auction_id {
                 return Err(AuctionError::InvalidAuctionId(auction_id));
             }
 
-            auction_state.bids.push(bid);
+            let logs_open = !matches!(
+                auction_state.phase,
+                AuctionPhase::Ended | AuctionPhase::Cancelled
+            );
+            if logs_open {
+                for removal in dropped {
+                    auction_state.log_dropped_submission(removal.submission_hash, removal.reason);
+                }
+            }
+
+            // Commit-reveal auctions only accept bids through `reveal_bid`,
+            // and encrypted auctions only through `submit_encrypted_bid`
+            let batch_error = match self.auction_mode {
+                _ if auction_state.is_ended => Some(AuctionError::AuctionEnded),
+                AuctionMode::CommitReveal { .. } => Some(AuctionError::CommitmentRequired),
+                AuctionMode::Encrypted => Some(AuctionError::EncryptedBidRequired),
+                _ => None,
+            };
+            if let Some(e) = batch_error {
+                if logs_open {
+                    for bid in bids {
+                        auction_state.log_dropped_submission(bid.hash(), RemovalReason::Rejected);
+                    }
+                }
+                return Err(e);
+            }
+
+            // Invalid bids (e.g., below the reserve price) are dropped from the batch
+            let mut leader_arrival_time = None;
+            let mut rejected = 0;
+            for bid in bids {
+                let arrival_time = bid.arrival_time;
+                let bid_hash = bid.hash();
+                match self.accept_bid(auction_state, bid) {
+                    Ok(placement) => {
+                        leader_arrival_time =
+                            leader_arrival_time.max(placement.is_leading.then_some(arrival_time))
+                    }
+                    Err(_) => {
+                        auction_state.log_dropped_submission(bid_hash, RemovalReason::Rejected);
+                        rejected += 1;
+                    }
+                }
+            }
+            if rejected > 0 {
+                eprintln!(
+                    "[Worker {}] Dropped {} invalid bid(s) for auction {}",
+                    self.chain_id, rejected, auction_id
+                );
+            }
+
+            self.after_bids_accepted(auction_state, leader_arrival_time)
+                .await;
+
+            Ok(())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Withdraws the bidder's bid before the auction's end time. The cancellation must be signed
+    /// by the bidder, and its nonce must be at least the nonce of the bid being withdrawn.
+    pub async fn cancel_bid(
+        &self,
+        auction_id: AuctionId,
+        cancellation: BidCancellation,
+    ) -> Result<String, AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.is_ended || current_unix_ms() >= auction_state.end_time {
+                return Err(AuctionError::AuctionEnded);
+            }
+
+            if auction_state.auction_info.id != auction_id || cancellation.auction_id != auction_id
+            {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
+            }
+
+            if !self.signature_scheme.verifier().verify(
+                &cancellation.bidder_addr,
+                &cancellation.signing_hash(),
+                &cancellation.bidder_signature,
+            ) {
+                return Err(AuctionError::InvalidBuyerSignature);
+            }
+
+            let bidder_addr = &cancellation.bidder_addr;
+            let has_bid = auction_state
+                .bids
+                .iter()
+                .any(|b| &b.bidder_addr == bidder_addr)
+                || auction_state
+                    .encrypted_bids
+                    .iter()
+                    .any(|b| &b.bidder_addr == bidder_addr);
+            if !has_bid {
+                return Err(AuctionError::BidNotFound(bidder_addr.clone()));
+            }
+            if let Some(&current_nonce) = auction_state.bidder_nonces.get(bidder_addr) {
+                if cancellation.nonce < current_nonce {
+                    return Err(AuctionError::StaleBidNonce {
+                        nonce: cancellation.nonce,
+                        current_nonce,
+                    });
+                }
+            }
+
+            // Encrypted bids cannot be matched by nonce yet, so all of the bidder's are withdrawn.
+            // Replays of them are rejected at decryption by the recorded nonce.
+            let cancelled: Vec<String> = auction_state
+                .bids
+                .iter()
+                .filter(|b| &b.bidder_addr == bidder_addr)
+                .map(|b| b.hash())
+                .chain(
+                    auction_state
+                        .encrypted_bids
+                        .iter()
+                        .filter(|b| &b.bidder_addr == bidder_addr)
+                        .map(|b| b.hash()),
+                )
+                .collect();
+            auction_state.bids.retain(|b| &b.bidder_addr != bidder_addr);
+            auction_state
+                .encrypted_bids
+                .retain(|b| &b.bidder_addr != bidder_addr);
+            for submission_hash in cancelled {
+                auction_state.record_removal(submission_hash, RemovalReason::Cancelled);
+            }
+            auction_state
+                .bidder_nonces
+                .insert(bidder_addr.clone(), cancellation.nonce);
+            self.determine_winner(auction_state);
 
             Ok(format!(
-                "[Worker {}] ACK: Auction {} bid accepted.",
+                "[Worker {}] ACK: Auction {} bid cancelled.",
                 self.chain_id, auction_id
             ))
         } else {
@@ -99,143 +421,2472 @@ impl AuctionWorker {
         }
     }
 
-    pub async fn submit_bid_batch(
+    /// Submits a bid commitment during the bidding phase of a commit-reveal auction.
+    pub async fn submit_commitment(
         &self,
         auction_id: AuctionId,
-        bids: Vec<Bid>,
-    ) -> Result<(), AuctionError> {
+        commitment: BidCommitment,
+    ) -> Result<String, AuctionError> {
         let mut guard = self.state.write().await;
         if let Some(ref mut auction_state) = *guard {
-            if auction_state.is_ended {
+            if !matches!(self.auction_mode, AuctionMode::CommitReveal { .. }) {
+                return Err(AuctionError::CommitmentNotAccepted);
+            }
+
+            if auction_state.phase != AuctionPhase::Bidding {
                 return Err(AuctionError::AuctionEnded);
             }
 
-            // Potential place to check if the provided auction_id matches the current state's ID
-            if auction_state.auction_info.id != auction_id {
+            if auction_state.auction_info.id != auction_id || commitment.auction_id != auction_id {
                 return Err(AuctionError::InvalidAuctionId(auction_id));
             }
 
-            auction_state.bids.extend(bids);
+            if !self.signature_scheme.verifier().verify(
+                &commitment.bidder_addr,
+                &commitment.signing_hash(),
+                &commitment.bidder_signature,
+            ) {
+                return Err(AuctionError::InvalidBuyerSignature);
+            }
+
+            // Commitments cannot be ranked, so they are simply refused once a cap is hit
+            self.check_sealed_limits(
+                auction_state.commitments.iter().map(|c| &c.bidder_addr),
+                &commitment.bidder_addr,
+            )?;
+            auction_state.log_submission(commitment.hash());
+            auction_state.commitments.push(commitment);
 
-            Ok(())
+            Ok(format!(
+                "[Worker {}] ACK: Auction {} commitment accepted.",
+                self.chain_id, auction_id
+            ))
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    /// Returns the current auction's ID and `AuctionInfo`.
-    pub async fn request_sale_info(&self) -> Result<(AuctionId, AuctionInfo), AuctionError> {
-        let guard = self.state.read().await;
-        if let Some(ref auction_state) = *guard {
-            let info = auction_state.auction_info.clone();
-            let auction_id = info.id.clone();
-            Ok((auction_id, info))
+    /// Reveals a previously committed bid during the reveal phase of a commit-reveal auction.
+    /// The bid is accepted only if `bid.commitment(salt)` matches a commitment of the same bidder,
+    /// and is stamped with its arrival sequence number and time at the reveal.
+    pub async fn reveal_bid(
+        &self,
+        auction_id: AuctionId,
+        mut bid: Bid,
+        salt: String,
+    ) -> Result<String, AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.phase != AuctionPhase::Reveal {
+                return Err(AuctionError::NotInRevealPhase);
+            }
+
+            if auction_state.auction_info.id != auction_id || bid.auction_id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
+            }
+
+            let commitment = bid.commitment(&salt);
+            let position = auction_state
+                .commitments
+                .iter()
+                .position(|c| c.bidder_addr == bid.bidder_addr && c.commitment == commitment)
+                .ok_or(AuctionError::InvalidReveal)?;
+
+            let mut sequences_guard = self.bid_sequences.lock().await;
+            let next_sequence = stamp_arrival(&mut sequences_guard, &mut bid);
+            self.accept_bid(auction_state, bid)?;
+            *next_sequence += 1;
+            drop(sequences_guard);
+            let commitment = auction_state.commitments.remove(position);
+            auction_state.record_removal(commitment.hash(), RemovalReason::Opened);
+
+            Ok(format!(
+                "[Worker {}] ACK: Auction {} bid revealed.",
+                self.chain_id, auction_id
+            ))
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    /// Returns the most recent ToB (Top-of-Block) information, i.e., the list of transactions
-    /// from the current highest bidder. If there is no winner yet, returns an empty list.
-    pub async fn get_latest_tob(&self) -> Result<Vec<Tx>, AuctionError> {
-        let guard = self.state.read().await;
-        if let Some(ref auction_state) = *guard {
-            if let Some(ref winner_addr) = auction_state.winner {
-                let tx_list = auction_state
-                    .bids
-                    .iter()
-                    .find(|b| &b.bidder_addr == winner_addr)
-                    .map(|b| b.tx_list.clone())
-                    .unwrap_or_default();
-                Ok(tx_list)
-            } else {
-                Ok(Vec::new())
+    /// Submits a bid encrypted to the public key of the current encrypted auction.
+    /// The bid is only decrypted, validated, and ranked once the auction ends.
+    pub async fn submit_encrypted_bid(
+        &self,
+        auction_id: AuctionId,
+        mut encrypted_bid: EncryptedBid,
+    ) -> Result<String, AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if self.auction_mode != AuctionMode::Encrypted {
+                return Err(AuctionError::EncryptedBidNotAccepted);
+            }
+
+            if auction_state.is_ended {
+                return Err(AuctionError::AuctionEnded);
+            }
+
+            if auction_state.auction_info.id != auction_id || encrypted_bid.auction_id != auction_id
+            {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
             }
+
+            // Encrypted bids cannot be ranked, so they are simply refused once a cap is hit
+            self.check_sealed_limits(
+                auction_state.encrypted_bids.iter().map(|b| &b.bidder_addr),
+                &encrypted_bid.bidder_addr,
+            )?;
+
+            // Taken from the auction's counter, which only increases, so that a number is never
+            // reused after a cancellation removes an encrypted bid
+            let mut sequences_guard = self.bid_sequences.lock().await;
+            let next_sequence = sequences_guard.entry(auction_id.clone()).or_insert(0);
+            encrypted_bid.sequence = *next_sequence;
+            encrypted_bid.arrival_time = current_unix_ms();
+            *next_sequence += 1;
+            drop(sequences_guard);
+            auction_state.log_submission(encrypted_bid.hash());
+            auction_state.encrypted_bids.push(encrypted_bid);
+
+            Ok(format!(
+                "[Worker {}] ACK: Auction {} encrypted bid accepted.",
+                self.chain_id, auction_id
+            ))
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    /// Returns the entire current auction state.
-    pub async fn get_auction_state(&self) -> Result<AuctionState, AuctionError> {
+    /// Returns the public key that bids to the current encrypted auction must be encrypted to.
+    pub async fn get_encryption_public_key(&self) -> Result<String, AuctionError> {
         let guard = self.state.read().await;
         if let Some(ref auction_state) = *guard {
-            Ok(auction_state.clone())
+            auction_state
+                .encryption_public_key
+                .clone()
+                .ok_or(AuctionError::EncryptedBidNotAccepted)
         } else {
             Err(AuctionError::NoAuctions)
         }
     }
 
-    // ------------------------------------------------------------------------
-    // Internal loop handling: determines if an auction should end, picks the highest bidder, etc.
-    // ------------------------------------------------------------------------
-    async fn process_auction(&self) -> Result<(), String> {
+    /// Validates a bid for a queued auction of the chain and adds it to `staged`, the state the
+    /// bid is kept in until the auction starts. The bid goes through the same checks and caps as
+    /// a bid for the ongoing auction. Only sealed-bid auctions take bids before they start.
+    ///
+    /// Unless `stamped` is set for a bid that `BidService` has already stamped, the bid is
+    /// stamped with its arrival sequence number and time here. `BidService` issued a receipt for
+    /// a stamped bid, so it is logged as a removal if it is rejected.
+    pub async fn stage_bid(
+        &self,
+        staged: &mut AuctionState,
+        mut bid: Bid,
+        stamped: bool,
+    ) -> Result<(), AuctionError> {
+        if self.auction_mode != AuctionMode::SealedBid {
+            return Err(AuctionError::AuctionNotStarted);
+        }
+
+        if bid.auction_id != staged.auction_info.id {
+            return Err(AuctionError::InvalidAuctionId(bid.auction_id));
+        }
+
+        if stamped {
+            let bid_hash = bid.hash();
+            return self.accept_bid(staged, bid).map(|_| ()).inspect_err(|_| {
+                staged.log_dropped_submission(bid_hash, RemovalReason::Rejected);
+            });
+        }
+        let mut sequences_guard = self.bid_sequences.lock().await;
+        let next_sequence = stamp_arrival(&mut sequences_guard, &mut bid);
+        self.accept_bid(staged, bid)?;
+        *next_sequence += 1;
+        Ok(())
+    }
+
+    /// Assigns the top of the block to the leg of a winning package bid. The remaining blockspace
+    /// is allocated to the single-block bids as usual. This also applies to an auction that
+    /// has just closed and is being settled, whose outcome is then recomputed. The leg is stamped
+    /// with its arrival sequence number and time on assignment.
+    pub async fn assign_package_leg(
+        &self,
+        auction_id: AuctionId,
+        mut leg: Bid,
+    ) -> Result<(), AuctionError> {
         let mut guard = self.state.write().await;
         if let Some(ref mut auction_state) = *guard {
-            // If the auction is already ended, do nothing
-            if auction_state.is_ended {
-                return Ok(());
+            if auction_state.auction_info.id != auction_id || leg.auction_id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
             }
 
-            let now = current_unix_ms();
-            let info = &auction_state.auction_info;
+            if matches!(
+                auction_state.phase,
+                AuctionPhase::Ended | AuctionPhase::Cancelled
+            ) {
+                return Err(AuctionError::AuctionEnded);
+            }
 
-            // If the auction hasn't started yet, do nothing
-            if now < info.start_time {
-                return Ok(());
+            let mut sequences_guard = self.bid_sequences.lock().await;
+            *stamp_arrival(&mut sequences_guard, &mut leg) += 1;
+            drop(sequences_guard);
+            auction_state.log_submission(leg.hash());
+            if let Some(previous) = auction_state.package_leg.replace(leg) {
+                auction_state.record_removal(previous.hash(), RemovalReason::Replaced);
+            }
+            self.determine_winner(auction_state);
+            Ok(())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Withdraws a bid by its hash and recomputes the outcome. Used to take back the legs of a
+    /// cross-chain bid that lost on another chain, while the auction is open or being settled.
+    pub async fn withdraw_bid(
+        &self,
+        auction_id: AuctionId,
+        bid_hash: &str,
+    ) -> Result<(), AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.auction_info.id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
             }
 
-            // Check if auction has ended
-            if now >= info.end_time {
-                auction_state.is_ended = true;
-                // Sort bids by highest amount
-                auction_state
-                    .bids
-                    .sort_unstable_by(|a, b| b.bid_amount.cmp(&a.bid_amount));
-                if let Some(top_bid) = auction_state.bids.first() {
-                    auction_state.highest_bid = top_bid.bid_amount;
-                    auction_state.winner = Some(top_bid.bidder_addr.clone());
-                }
+            if matches!(
+                auction_state.phase,
+                AuctionPhase::Ended | AuctionPhase::Cancelled
+            ) {
+                return Err(AuctionError::AuctionEnded);
+            }
 
-                let auction_id = info.id.clone();
-                self.send_worker_message(WorkerMessageType::AuctionEnded, auction_id)
-                    .await?;
+            let position = auction_state
+                .bids
+                .iter()
+                .position(|b| b.hash() == bid_hash)
+                .ok_or(AuctionError::BidNotFound(bid_hash.to_string()))?;
+            auction_state.bids.remove(position);
+            auction_state.record_removal(bid_hash.to_string(), RemovalReason::Withdrawn);
+            self.determine_winner(auction_state);
+            Ok(())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
 
-                return Ok(());
+    /// Undoes the placement of a cross-chain leg whose other legs could not be placed: the leg is
+    /// removed, and the bid it replaced or evicted is restored along with the bidder's nonce.
+    /// Nothing is restored if the bidder has placed a newer bid since.
+    pub async fn revert_bid(
+        &self,
+        auction_id: AuctionId,
+        placement: BidPlacement,
+    ) -> Result<(), AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.auction_info.id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
             }
 
-            // If the auction is ongoing, you could sort bids to always know the highest
-            auction_state
+            if matches!(
+                auction_state.phase,
+                AuctionPhase::Ended | AuctionPhase::Cancelled
+            ) {
+                return Err(AuctionError::AuctionEnded);
+            }
+
+            let position = auction_state
                 .bids
-                .sort_unstable_by(|a, b| b.bid_amount.cmp(&a.bid_amount));
-            if let Some(top_bid) = auction_state.bids.first() {
-                auction_state.highest_bid = top_bid.bid_amount;
-                auction_state.winner = Some(top_bid.bidder_addr.clone());
+                .iter()
+                .position(|b| b.hash() == placement.bid_hash)
+                .ok_or(AuctionError::BidNotFound(placement.bid_hash.clone()))?;
+            let reverted = auction_state.bids.remove(position);
+            auction_state.record_removal(placement.bid_hash.clone(), RemovalReason::Reverted);
+            match placement.previous_nonce {
+                Some(nonce) => auction_state
+                    .bidder_nonces
+                    .insert(reverted.bidder_addr, nonce),
+                None => auction_state.bidder_nonces.remove(&reverted.bidder_addr),
+            };
+            for restored in placement.replaced.into_iter().chain(placement.evicted) {
+                auction_state.log_submission(restored.hash());
+                auction_state.bids.push(restored);
             }
-            self.send_worker_message(WorkerMessageType::AuctionProcessing, info.id.clone())
-                .await?;
+            // The manager holds back settlement while it places the legs, so no outcome has
+            // been settled on this auction yet and it can be recomputed even if it has closed
+            self.determine_winner(auction_state);
+            Ok(())
+        } else {
+            Err(AuctionError::NoAuctions)
         }
-        Ok(())
     }
 
-    // ------------------------------------------------------------------------
-    // Helper methods
-    // ------------------------------------------------------------------------
-
-    /// Sends a `WorkerMessage` to the manager.
-    async fn send_worker_message(
+    /// Ends an auction that has been settled, and returns its final state. The Merkle
+    /// commitment over its bids is built at this point.
+    pub async fn finalize_auction(
         &self,
-        message_type: WorkerMessageType,
         auction_id: AuctionId,
-    ) -> Result<(), String> {
-        let message = WorkerMessage {
-            message_type,
-            chain_id: self.chain_id,
-            auction_id,
-        };
-        self.result_sender
-            .send(message)
-            .await
-            .map_err(|e| format!("Failed to send auction message: {}", e))
+    ) -> Result<AuctionState, AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.auction_info.id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
+            }
+
+            if auction_state.phase != AuctionPhase::Settling {
+                return Err(AuctionError::AuctionNotSettling);
+            }
+
+            auction_state.end();
+            auction_state.commit_bids();
+            Ok(auction_state.clone())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Aborts the current auction without an outcome, e.g., because its seller cancelled it.
+    pub async fn abort_auction(&self, auction_id: AuctionId) -> Result<(), AuctionError> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            if auction_state.auction_info.id != auction_id {
+                return Err(AuctionError::InvalidAuctionId(auction_id));
+            }
+
+            if auction_state.is_ended {
+                return Err(AuctionError::AuctionEnded);
+            }
+
+            auction_state.cancel();
+            *self.keypair.write().await = None;
+            println!(
+                "[Worker {}] Auction {} was aborted.",
+                self.chain_id, auction_id
+            );
+            Ok(())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Returns the current highest bid of an open ascending (English) auction, or the current
+    /// asking price of a descending-price (Dutch) auction. Returns an error for sealed-bid
+    /// auctions, whose bids stay hidden until the auction ends.
+    pub async fn get_current_price(&self) -> Result<u64, AuctionError> {
+        let guard = self.state.read().await;
+        if let Some(ref auction_state) = *guard {
+            match self.auction_mode {
+                AuctionMode::English { .. } => Ok(auction_state.highest_bid),
+                AuctionMode::Dutch {
+                    start_price,
+                    floor_price,
+                    step_ms,
+                } => Ok(dutch_price(
+                    &auction_state.auction_info,
+                    start_price,
+                    floor_price,
+                    step_ms,
+                    current_unix_ms(),
+                )),
+                AuctionMode::SealedBid
+                | AuctionMode::CommitReveal { .. }
+                | AuctionMode::Encrypted => Err(AuctionError::SealedBidAuction),
+            }
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Returns the current auction's ID and `AuctionInfo`.
+    pub async fn request_sale_info(&self) -> Result<(AuctionId, AuctionInfo), AuctionError> {
+        let guard = self.state.read().await;
+        if let Some(ref auction_state) = *guard {
+            let info = auction_state.auction_info.clone();
+            let auction_id = info.id.clone();
+            Ok((auction_id, info))
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Returns the most recent ToB (Top-of-Block) information, i.e., the transactions of the
+    /// current winners in winner order. If there is no winner yet, returns an empty list.
+    pub async fn get_latest_tob(&self) -> Result<Vec<Tx>, AuctionError> {
+        let guard = self.state.read().await;
+        if let Some(ref auction_state) = *guard {
+            let tx_list = auction_state
+                .winning_bids()
+                .into_iter()
+                .flat_map(|b| b.tx_list.clone())
+                .collect();
+            Ok(tx_list)
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Returns the ordered block segment sold by the current auction: the winners' transactions
+    /// first, in winner order, followed by the transactions of the remaining bids in the order of
+    /// the worker's `OrderingRule`. A remaining bid is included only if it still fits into the
+    /// auction's `blockspace_size`.
+    pub async fn get_block_ordering(&self) -> Result<Vec<OrderedTx>, AuctionError> {
+        let guard = self.state.read().await;
+        if let Some(ref auction_state) = *guard {
+            let winning_bids = auction_state.winning_bids();
+
+            let mut remaining_space = auction_state
+                .auction_info
+                .blockspace_size
+                .saturating_sub(winning_bids.iter().map(|b| b.blockspace_used()).sum());
+            let mut other_bids: Vec<&Bid> = auction_state
+                .bids
+                .iter()
+                .filter(|b| !winning_bids.iter().any(|w| w.hash() == b.hash()))
+                .collect();
+            let tie_break_rule = auction_state.tie_break_rule;
+            other_bids.sort_by(|a, b| self.ordering_rule.compare(a, b, tie_break_rule));
+            let mut filler_bids = Vec::new();
+            for b in other_bids {
+                if b.blockspace_used() <= remaining_space {
+                    remaining_space -= b.blockspace_used();
+                    filler_bids.push(b);
+                }
+            }
+
+            let ordering = winning_bids
+                .into_iter()
+                .map(|b| (b, true))
+                .chain(filler_bids.into_iter().map(|b| (b, false)))
+                .flat_map(|(b, is_winner)| {
+                    let bid_hash = b.hash();
+                    b.tx_list
+                        .iter()
+                        .map(move |tx| (b, bid_hash.clone(), tx, is_winner))
+                })
+                .enumerate()
+                .map(|(position, (b, bid_hash, tx, is_winner))| OrderedTx {
+                    position,
+                    tx: tx.clone(),
+                    bidder_addr: b.bidder_addr.clone(),
+                    bid_hash,
+                    is_winner,
+                })
+                .collect();
+            Ok(ordering)
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    /// Returns the entire current auction state.
+    pub async fn get_auction_state(&self) -> Result<AuctionState, AuctionError> {
+        let guard = self.state.read().await;
+        if let Some(ref auction_state) = *guard {
+            Ok(auction_state.clone())
+        } else {
+            Err(AuctionError::NoAuctions)
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Internal loop handling: determines if an auction should end, picks the highest bidder, etc.
+    // ------------------------------------------------------------------------
+    pub(crate) async fn process_auction(&self) -> Result<(), String> {
+        let mut guard = self.state.write().await;
+        if let Some(ref mut auction_state) = *guard {
+            // If the auction is already ended, do nothing
+            if auction_state.is_ended {
+                return Ok(());
+            }
+
+            let now = current_unix_ms();
+            let info = &auction_state.auction_info;
+            let auction_id = info.id.clone();
+
+            // If the auction hasn't started yet, do nothing
+            if now < info.start_time {
+                return Ok(());
+            }
+
+            // In commit-reveal mode, the end time closes the commitment phase and opens the reveal window
+            if let AuctionMode::CommitReveal { reveal_window_ms } = self.auction_mode {
+                if auction_state.phase == AuctionPhase::Bidding && now >= auction_state.end_time {
+                    auction_state.phase = AuctionPhase::Reveal;
+                    self.send_worker_message(WorkerMessageType::RevealStarted, auction_id)
+                        .await?;
+                    return Ok(());
+                }
+
+                if auction_state.phase == AuctionPhase::Reveal {
+                    if now < auction_state.end_time.saturating_add(reveal_window_ms) {
+                        return Ok(());
+                    }
+
+                    // Commitments that were never revealed are forfeited
+                    let unrevealed = std::mem::take(&mut auction_state.commitments);
+                    for commitment in unrevealed.iter() {
+                        auction_state.record_removal(commitment.hash(), RemovalReason::Forfeited);
+                    }
+                    auction_state.forfeited_commitments.extend(unrevealed);
+                    auction_state.close();
+                    self.determine_winner(auction_state);
+
+                    self.send_worker_message(WorkerMessageType::AuctionClosed, auction_id)
+                        .await?;
+
+                    return Ok(());
+                }
+            }
+
+            // Check if auction has ended, taking soft-close extensions into account
+            if now >= auction_state.end_time {
+                if self.auction_mode == AuctionMode::Encrypted {
+                    self.decrypt_bids(auction_state).await;
+                }
+                auction_state.close();
+                self.determine_winner(auction_state);
+
+                self.send_worker_message(WorkerMessageType::AuctionClosed, auction_id)
+                    .await?;
+
+                return Ok(());
+            }
+
+            // If the auction is ongoing, determine the current winner to always know the highest,
+            // unless no bid arrived since the last time
+            if auction_state.winners_outdated {
+                self.determine_winner(auction_state);
+            }
+            self.send_worker_message(WorkerMessageType::AuctionProcessing, auction_id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------------
+    // Helper methods
+    // ------------------------------------------------------------------------
+
+    /// Sorts bids by highest amount (resolving ties with the state's `TieBreakRule`), lets the
+    /// worker's `AuctionRule` allocate the blockspace left after the package leg (if any), and
+    /// updates the winners, highest bid, and clearing price accordingly.
+    fn determine_winner(&self, auction_state: &mut AuctionState) {
+        let tie_break_rule = auction_state.tie_break_rule;
+        auction_state
+            .bids
+            .sort_by(|a, b| tie_break_rule.compare(a, b));
+
+        match self.auction_mode {
+            AuctionMode::English { .. } => {
+                self.determine_english_winner(auction_state);
+                return;
+            }
+            // The winner is determined as soon as a bid meets the current price
+            AuctionMode::Dutch { .. } => return,
+            AuctionMode::SealedBid | AuctionMode::CommitReveal { .. } | AuctionMode::Encrypted => {}
+        }
+
+        let bids = &auction_state.bids;
+        let reserve_price = auction_state.auction_info.reserve_price.unwrap_or(0);
+
+        // The leg of a winning package bid takes the top of the block at its own amount
+        let package_winner = auction_state.package_leg.as_ref().map(|leg| AuctionWinner {
+            bidder_addr: leg.bidder_addr.clone(),
+            bid_hash: leg.hash(),
+            bid_amount: leg.effective_value(),
+            allocated_size: leg.blockspace_used(),
+            price: leg.effective_value(),
+        });
+        let capacity = auction_state
+            .auction_info
+            .blockspace_size
+            .saturating_sub(package_winner.as_ref().map_or(0, |w| w.allocated_size));
+
+        let selected = self
+            .auction_rule
+            .select_winners(bids, capacity, reserve_price);
+        let winners: Vec<AuctionWinner> = package_winner
+            .into_iter()
+            .chain(self.checked_winners(bids, capacity, reserve_price, selected))
+            .collect();
+
+        auction_state.highest_bid = bids.first().map(|bid| bid.effective_value()).unwrap_or(0);
+        auction_state.reserve_met = auction_state
+            .auction_info
+            .reserve_price
+            .is_none_or(|reserve_price| auction_state.highest_bid >= reserve_price);
+        auction_state.clearing_price = winners
+            .iter()
+            .fold(0u64, |total, winner| total.saturating_add(winner.price));
+        auction_state.winners = winners;
+        auction_state.winners_outdated = false;
+    }
+
+    /// Keeps only the winners selected by the `AuctionRule` that the auction can honour: each one
+    /// must refer to a distinct bid of the auction, be allocated at least the blockspace the bid
+    /// uses, and pay at least the reserve price but no more than the bid, and together they must
+    /// fit into `capacity`. The others are dropped and their number is logged.
+    fn checked_winners(
+        &self,
+        bids: &[Bid],
+        capacity: u64,
+        reserve_price: u64,
+        selected: Vec<AuctionWinner>,
+    ) -> Vec<AuctionWinner> {
+        let selected_count = selected.len();
+        let mut bid_hashes = HashSet::new();
+        let mut remaining = capacity;
+        let winners: Vec<AuctionWinner> = selected
+            .into_iter()
+            .filter(|winner| {
+                let valid = bids.iter().any(|bid| {
+                    bid.hash() == winner.bid_hash
+                        && bid.bidder_addr == winner.bidder_addr
+                        && winner.allocated_size >= bid.blockspace_used()
+                        && winner.price <= bid.effective_value()
+                }) && winner.price >= reserve_price
+                    && winner.allocated_size <= remaining
+                    && bid_hashes.insert(winner.bid_hash.clone());
+                if valid {
+                    remaining -= winner.allocated_size;
+                }
+                valid
+            })
+            .collect();
+
+        if winners.len() < selected_count {
+            eprintln!(
+                "[Worker {}] Dropped {} invalid winner(s) selected by the auction rule",
+                self.chain_id,
+                selected_count - winners.len()
+            );
+        }
+        winners
+    }
+
+    /// In an open ascending (English) auction, the highest bid wins the whole blockspace and pays
+    /// its own amount; the chain's `PricingRule` does not apply. Expects the bids to be sorted.
+    fn determine_english_winner(&self, auction_state: &mut AuctionState) {
+        auction_state.winners = auction_state
+            .bids
+            .first()
+            .map(|top_bid| AuctionWinner {
+                bidder_addr: top_bid.bidder_addr.clone(),
+                bid_hash: top_bid.hash(),
+                bid_amount: top_bid.effective_value(),
+                allocated_size: auction_state.auction_info.blockspace_size,
+                price: top_bid.effective_value(),
+            })
+            .into_iter()
+            .collect();
+        auction_state.highest_bid = auction_state
+            .bids
+            .first()
+            .map(|bid| bid.effective_value())
+            .unwrap_or(0);
+        auction_state.clearing_price = auction_state.highest_bid;
+        auction_state.reserve_met = auction_state
+            .auction_info
+            .reserve_price
+            .is_none_or(|reserve_price| auction_state.highest_bid >= reserve_price);
+    }
+
+    /// Checks the bidder's signature under the chain's `SignatureScheme`, then validates the bid
+    /// against the auction's rules and adds it to the state (see `admit_bid`). Every bid goes
+    /// through here, whether it is submitted, revealed, or decrypted.
+    fn accept_bid(
+        &self,
+        auction_state: &mut AuctionState,
+        bid: Bid,
+    ) -> Result<BidPlacement, AuctionError> {
+        if !self.signature_scheme.verifier().verify(
+            &bid.bidder_addr,
+            &bid.signing_hash(),
+            &bid.bidder_signature,
+        ) {
+            return Err(AuctionError::InvalidBuyerSignature);
+        }
+        self.admit_bid(auction_state, bid)
+    }
+
+    /// Validates a bid whose signature has been checked against the auction's rules and adds it
+    /// to the state.
+    ///
+    /// In an open ascending (English) auction, the bid must beat the current highest bid by at
+    /// least the configured minimum increment, and the leader is updated immediately.
+    /// Returns what accepting the bid changed, including whether it took the lead.
+    fn admit_bid(
+        &self,
+        auction_state: &mut AuctionState,
+        bid: Bid,
+    ) -> Result<BidPlacement, AuctionError> {
+        // A descending-price (Dutch) auction may close in the middle of a batch
+        if auction_state.is_ended {
+            return Err(AuctionError::AuctionEnded);
+        }
+
+        if bid.gas_used().is_none() {
+            return Err(AuctionError::GasOverflow);
+        }
+
+        // Otherwise anyone could resubmit a fixed-amount bid at no value under its signature,
+        // using up its nonce
+        if bid.price_per_gas == Some(0) {
+            return Err(AuctionError::ZeroPricePerGas);
+        }
+
+        // A bid only replaces the bidder's earlier bid if its nonce is higher
+        if let Some(&current_nonce) = auction_state.bidder_nonces.get(&bid.bidder_addr) {
+            if bid.nonce <= current_nonce {
+                return Err(AuctionError::StaleBidNonce {
+                    nonce: bid.nonce,
+                    current_nonce,
+                });
+            }
+        }
+
+        if let Some(reserve_price) = auction_state.auction_info.reserve_price {
+            if bid.effective_value() < reserve_price {
+                return Err(AuctionError::BidBelowReservePrice {
+                    bid_amount: bid.effective_value(),
+                    reserve_price,
+                });
+            }
+        }
+
+        let bid_hash = bid.hash();
+        let bidder_addr = bid.bidder_addr.clone();
+        let previous_nonce = auction_state.bidder_nonces.get(&bid.bidder_addr).copied();
+        let placement = |is_leading, replaced, evicted| BidPlacement {
+            is_leading,
+            bid_hash,
+            bidder_addr,
+            replaced,
+            evicted,
+            previous_nonce,
+        };
+
+        if let AuctionMode::Dutch {
+            start_price,
+            floor_price,
+            step_ms,
+        } = self.auction_mode
+        {
+            let current_price = dutch_price(
+                &auction_state.auction_info,
+                start_price,
+                floor_price,
+                step_ms,
+                current_unix_ms(),
+            );
+            if bid.effective_value() < current_price {
+                return Err(AuctionError::BidTooLow {
+                    current_price,
+                    minimum_bid: current_price,
+                });
+            }
+
+            // The first bid at or above the current price wins at that price and closes the auction
+            let reserve_price = auction_state.auction_info.reserve_price.unwrap_or(0);
+            auction_state.winners = vec![AuctionWinner {
+                bidder_addr: bid.bidder_addr.clone(),
+                bid_hash: bid.hash(),
+                bid_amount: bid.effective_value(),
+                allocated_size: auction_state.auction_info.blockspace_size,
+                price: current_price.max(reserve_price),
+            }];
+            auction_state.highest_bid = bid.effective_value();
+            auction_state.clearing_price = current_price.max(reserve_price);
+            auction_state.reserve_met = true;
+            auction_state.close();
+            let replaced = insert_bid(auction_state, bid);
+            return Ok(placement(true, replaced, None));
+        }
+
+        let leading_amount = auction_state.bids.iter().map(|b| b.effective_value()).max();
+
+        if let AuctionMode::English { min_increment } = self.auction_mode {
+            if let Some(current_price) = leading_amount {
+                let minimum_bid = current_price.saturating_add(min_increment);
+                if bid.effective_value() < minimum_bid {
+                    return Err(AuctionError::BidTooLow {
+                        current_price,
+                        minimum_bid,
+                    });
+                }
+            }
+
+            let evicted = self.make_room_for(auction_state, &bid)?;
+            let replaced = insert_bid(auction_state, bid);
+            self.determine_winner(auction_state);
+            return Ok(placement(true, replaced, evicted));
+        }
+
+        // Evict only once the bid is known to be valid, so that a rejected bid never costs
+        // another bid its place
+        let is_leading = leading_amount.is_none_or(|amount| bid.effective_value() > amount);
+        let evicted = self.make_room_for(auction_state, &bid)?;
+        let replaced = insert_bid(auction_state, bid);
+        Ok(placement(is_leading, replaced, evicted))
+    }
+
+    /// Enforces the per-auction bid cap before a bid is added. A bid that replaces the bidder's
+    /// earlier one always fits. Otherwise, if the auction is full, the worst bid is evicted when
+    /// the new bid ranks better than it, and the new bid is rejected when it does not.
+    /// Returns the evicted bid.
+    fn make_room_for(
+        &self,
+        auction_state: &mut AuctionState,
+        bid: &Bid,
+    ) -> Result<Option<Bid>, AuctionError> {
+        let Some(limit) = self.bid_limits.max_bids_per_auction else {
+            return Ok(None);
+        };
+        if auction_state.bids.len() < limit
+            || auction_state
+                .bids
+                .iter()
+                .any(|b| b.bidder_addr == bid.bidder_addr)
+        {
+            return Ok(None);
+        }
+
+        let tie_break_rule = auction_state.tie_break_rule;
+        let worst = auction_state
+            .bids
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| tie_break_rule.compare(a, b))
+            .map(|(index, worst)| (index, tie_break_rule.compare(bid, worst)));
+        match worst {
+            Some((index, Ordering::Less)) => {
+                let evicted = auction_state.bids.swap_remove(index);
+                auction_state.record_removal(evicted.hash(), RemovalReason::Evicted);
+                Ok(Some(evicted))
+            }
+            _ => Err(AuctionError::AuctionBidLimitReached { limit }),
+        }
+    }
+
+    /// Enforces both bid caps on submissions that cannot be ranked before the auction ends
+    /// (commitments and encrypted bids). `existing` yields the bidder of every stored submission.
+    fn check_sealed_limits<'a>(
+        &self,
+        existing: impl Iterator<Item = &'a String>,
+        bidder_addr: &str,
+    ) -> Result<(), AuctionError> {
+        let mut total = 0;
+        let mut from_bidder = 0;
+        for addr in existing {
+            total += 1;
+            if addr == bidder_addr {
+                from_bidder += 1;
+            }
+        }
+
+        if let Some(limit) = self.bid_limits.max_bids_per_bidder {
+            if from_bidder >= limit {
+                return Err(AuctionError::BidderBidLimitReached { limit });
+            }
+        }
+        if let Some(limit) = self.bid_limits.max_bids_per_auction {
+            if total >= limit {
+                return Err(AuctionError::AuctionBidLimitReached { limit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypts the whole batch of encrypted bids at the end of an encrypted auction and accepts
+    /// them in arrival order. Bids that fail to decrypt or validate are dropped; only their count
+    /// is logged so that nothing about them leaks. The auction's secret key is discarded afterwards.
+    async fn decrypt_bids(&self, auction_state: &mut AuctionState) {
+        let Some(keypair) = self.keypair.write().await.take() else {
+            return;
+        };
+
+        let mut rejected = 0;
+        for encrypted_bid in std::mem::take(&mut auction_state.encrypted_bids) {
+            let encrypted_bid_hash = encrypted_bid.hash();
+            let accepted = keypair
+                .decrypt_bid(&encrypted_bid)
+                .ok()
+                .map(|mut bid| {
+                    bid.sequence = encrypted_bid.sequence;
+                    bid.arrival_time = encrypted_bid.arrival_time;
+                    bid
+                })
+                .is_some_and(|bid| self.accept_bid(auction_state, bid).is_ok());
+            if accepted {
+                auction_state.record_removal(encrypted_bid_hash, RemovalReason::Opened);
+            } else {
+                auction_state.record_removal(encrypted_bid_hash, RemovalReason::Rejected);
+                rejected += 1;
+            }
+        }
+
+        if rejected > 0 {
+            eprintln!(
+                "[Worker {}] Dropped {} invalid encrypted bid(s) for auction {}",
+                self.chain_id, rejected, auction_state.auction_info.id
+            );
+        }
+    }
+
+    /// Runs the follow-up actions after bids were accepted: notifies the manager right away if a
+    /// bid closed a descending-price (Dutch) auction, or otherwise applies the soft-close rule
+    /// when a new leading bid arrived at `leader_arrival_time`.
+    async fn after_bids_accepted(
+        &self,
+        auction_state: &mut AuctionState,
+        leader_arrival_time: Option<u64>,
+    ) {
+        if auction_state.is_ended {
+            if let Err(e) = self
+                .send_worker_message(
+                    WorkerMessageType::AuctionClosed,
+                    auction_state.auction_info.id.clone(),
+                )
+                .await
+            {
+                eprintln!("[Worker {}] {}", self.chain_id, e);
+            }
+        } else if let Some(arrival_time) = leader_arrival_time {
+            self.extend_if_sniped(auction_state, arrival_time).await;
+        }
+    }
+
+    /// Applies the soft-close rule after a new leading bid was accepted: if the bid arrived within
+    /// the final window before the end time, the end time is extended (up to the configured maximum)
+    /// and the manager is notified with `WorkerMessageType::AuctionExtended`. The window is judged
+    /// by the bid's arrival time rather than by when it was flushed to the worker.
+    async fn extend_if_sniped(&self, auction_state: &mut AuctionState, arrival_time: u64) {
+        let Some(soft_close) = self.soft_close else {
+            return;
+        };
+
+        if arrival_time >= auction_state.end_time
+            || arrival_time < auction_state.end_time.saturating_sub(soft_close.window_ms)
+        {
+            return;
+        }
+
+        let max_end_time = auction_state
+            .auction_info
+            .end_time
+            .saturating_add(soft_close.max_extension_ms);
+        let extended_end_time = arrival_time
+            .saturating_add(soft_close.extension_ms)
+            .min(max_end_time);
+        if extended_end_time <= auction_state.end_time {
+            return;
+        }
+
+        auction_state.end_time = extended_end_time;
+        if let Err(e) = self
+            .send_worker_message(
+                WorkerMessageType::AuctionExtended(extended_end_time),
+                auction_state.auction_info.id.clone(),
+            )
+            .await
+        {
+            eprintln!("[Worker {}] {}", self.chain_id, e);
+        }
+    }
+
+    /// Sends a `WorkerMessage` to the manager.
+    async fn send_worker_message(
+        &self,
+        message_type: WorkerMessageType,
+        auction_id: AuctionId,
+    ) -> Result<(), String> {
+        let message = WorkerMessage {
+            message_type,
+            chain_id: self.chain_id,
+            auction_id,
+        };
+        self.result_sender
+            .send(message)
+            .await
+            .map_err(|e| format!("Failed to send auction message: {}", e))
+    }
+}
+
+/// Stamps a bid that did not come through `BidService` with its auction's next arrival sequence
+/// number and the current time, overriding whatever the client set. Returns the auction's
+/// counter, which the caller increments once the bid is accepted, so that rejected bids do not
+/// consume a number.
+fn stamp_arrival<'a>(bid_sequences: &'a mut HashMap<AuctionId, u64>, bid: &mut Bid) -> &'a mut u64 {
+    let next_sequence = bid_sequences.entry(bid.auction_id.clone()).or_insert(0);
+    bid.sequence = *next_sequence;
+    bid.arrival_time = current_unix_ms();
+    next_sequence
+}
+
+/// Adds a bid to the state and its bid log, replacing any earlier bid of the same bidder, and
+/// records its nonce. Returns the replaced bid.
+fn insert_bid(auction_state: &mut AuctionState, bid: Bid) -> Option<Bid> {
+    let replaced = auction_state
+        .bids
+        .iter()
+        .position(|b| b.bidder_addr == bid.bidder_addr)
+        .map(|position| auction_state.bids.remove(position));
+    if let Some(replaced) = &replaced {
+        auction_state.record_removal(replaced.hash(), RemovalReason::Replaced);
+    }
+    auction_state.log_submission(bid.hash());
+    auction_state
+        .bidder_nonces
+        .insert(bid.bidder_addr.clone(), bid.nonce);
+    auction_state.bids.push(bid);
+    auction_state.winners_outdated = true;
+    replaced
+}
+
+/// Computes the asking price of a descending-price (Dutch) auction at `now`.
+///
+/// The price starts at `start_price` at the auction's `start_time` and decays linearly to
+/// `floor_price` at its `end_time`, moving in discrete steps of `step_ms` (continuously if zero).
+pub fn dutch_price(
+    info: &AuctionInfo,
+    start_price: u64,
+    floor_price: u64,
+    step_ms: u64,
+    now: u64,
+) -> u64 {
+    if now <= info.start_time || start_price <= floor_price {
+        return start_price.max(floor_price);
+    }
+    if now >= info.end_time {
+        return floor_price;
+    }
+
+    let duration = (info.end_time - info.start_time) as u128;
+    let mut elapsed = (now - info.start_time) as u128;
+    if step_ms > 0 {
+        elapsed -= elapsed % step_ms as u128;
+    }
+
+    let decay = (start_price - floor_price) as u128 * elapsed / duration;
+    start_price - decay as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{
+        core::domain::{PricingRule, TieBreakRule},
+        utils::{encryption::encrypt_bid, helpers::keccak256, signing::Secp256k1Key},
+    };
+
+    /// The test bidders, each signing with a key derived from its name.
+    const BIDDERS: [&str; 5] = [
+        "0xBidder1",
+        "0xBidder2",
+        "0xBidder3",
+        "0xBidder4",
+        "0xPackage",
+    ];
+
+    fn key_of(name: &str) -> Secp256k1Key {
+        Secp256k1Key::from_hex(&hex::encode(keccak256(&[name.as_bytes()]))).unwrap()
+    }
+
+    /// Returns the address of a test bidder.
+    fn addr(name: &str) -> String {
+        key_of(name).address()
+    }
+
+    /// Returns the name of a test bidder by its address.
+    fn name_of(bidder_addr: &str) -> &'static str {
+        BIDDERS
+            .into_iter()
+            .find(|name| addr(name) == bidder_addr)
+            .unwrap_or("unknown")
+    }
+
+    /// Signs a bid of a test bidder with its key, e.g., after changing its fields.
+    fn signed(mut bid: Bid) -> Bid {
+        let key = key_of(name_of(&bid.bidder_addr));
+        bid.bidder_signature = key.sign(&bid.signing_hash()).unwrap();
+        bid
+    }
+
+    fn ended_auction_info() -> AuctionInfo {
+        let now = current_unix_ms();
+        AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 2000,
+            now - 1000,
+            "0xSellerSignature".to_string(),
+        )
+    }
+
+    /// Creates a bid signed by the test bidder `name`.
+    fn bid(auction_id: &AuctionId, name: &str, bid_amount: u64, blockspace_size: u64) -> Bid {
+        signed(Bid {
+            chain_id: 1,
+            auction_id: auction_id.clone(),
+            bidder_addr: addr(name),
+            bid_amount,
+            blockspace_size,
+            tx_list: vec![Tx {
+                tx_data: format!("{}Tx", name),
+                gas_limit: blockspace_size,
+            }],
+            ..Default::default()
+        })
+    }
+
+    /// Runs an already-ended auction with one bid per `(bid_amount, blockspace_size)` pair,
+    /// submitted by `0xBidder1`, `0xBidder2`, ... in order.
+    async fn run_to_end(pricing_rule: PricingRule, bids: &[(u64, u64)]) -> AuctionState {
+        run_to_end_with_info(pricing_rule, ended_auction_info(), bids).await
+    }
+
+    async fn run_to_end_with_info(
+        pricing_rule: PricingRule,
+        info: AuctionInfo,
+        bids: &[(u64, u64)],
+    ) -> AuctionState {
+        let bids = bids
+            .iter()
+            .enumerate()
+            .map(|(i, (amount, size))| bid(&info.id, &format!("0xBidder{}", i + 1), *amount, *size))
+            .collect();
+        settle_bids(pricing_rule, info, bids).await
+    }
+
+    /// Starts an already-ended auction, submits `bids` as one batch, and settles it.
+    async fn settle_bids(
+        pricing_rule: PricingRule,
+        info: AuctionInfo,
+        bids: Vec<Bid>,
+    ) -> AuctionState {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            pricing_rule,
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        worker
+            .submit_bid_batch(auction_id, bids, Vec::new())
+            .await
+            .unwrap();
+
+        worker.process_auction().await.unwrap();
+        worker.get_auction_state().await.unwrap()
+    }
+
+    /// Returns the names of the winning test bidders.
+    fn winner_addrs(state: &AuctionState) -> Vec<&'static str> {
+        state
+            .winners
+            .iter()
+            .map(|winner| name_of(&winner.bidder_addr))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_first_price_winner_pays_own_bid() {
+        let state = run_to_end(
+            PricingRule::FirstPrice,
+            &[(1000, 500), (1500, 500), (1200, 500)],
+        )
+        .await;
+
+        assert!(state.is_ended);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+        assert_eq!(state.highest_bid, 1500);
+        assert_eq!(state.clearing_price, 1500);
+    }
+
+    #[tokio::test]
+    async fn test_second_price_winner_pays_runner_up_bid() {
+        let state = run_to_end(
+            PricingRule::SecondPrice,
+            &[(1000, 500), (1500, 500), (1200, 500)],
+        )
+        .await;
+
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+        assert_eq!(state.highest_bid, 1500);
+        assert_eq!(state.clearing_price, 1200);
+    }
+
+    #[tokio::test]
+    async fn test_second_price_single_bid_pays_zero() {
+        let state = run_to_end(PricingRule::SecondPrice, &[(1000, 500)]).await;
+
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+        assert_eq!(state.clearing_price, 0);
+    }
+
+    #[tokio::test]
+    async fn test_blockspace_is_allocated_to_revenue_maximizing_bids() {
+        // The single largest bid (1500) loses to two smaller bids that together pay 1800.
+        let state = run_to_end(
+            PricingRule::FirstPrice,
+            &[(1000, 300), (1500, 400), (800, 200), (100, 100)],
+        )
+        .await;
+
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1", "0xBidder3"]);
+        assert_eq!(
+            state
+                .winners
+                .iter()
+                .map(|winner| winner.allocated_size)
+                .collect::<Vec<_>>(),
+            vec![300, 200]
+        );
+        assert_eq!(state.highest_bid, 1500);
+        assert_eq!(state.clearing_price, 1800);
+    }
+
+    #[tokio::test]
+    async fn test_second_price_charges_vcg_prices_to_multiple_winners() {
+        let state = run_to_end(
+            PricingRule::SecondPrice,
+            &[(1000, 300), (1500, 400), (800, 200), (100, 100)],
+        )
+        .await;
+
+        // Without 0xBidder1 the others earn 1600 (0xBidder2 + 0xBidder4) instead of 800.
+        // Without 0xBidder3 the others earn 1600 (0xBidder2 + 0xBidder4) instead of 1000.
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1", "0xBidder3"]);
+        assert_eq!(
+            state
+                .winners
+                .iter()
+                .map(|winner| winner.price)
+                .collect::<Vec<_>>(),
+            vec![800, 600]
+        );
+        assert_eq!(state.clearing_price, 1400);
+    }
+
+    #[tokio::test]
+    async fn test_allocation_saturates_and_stays_bounded() {
+        // Two bids whose total overflows a u64
+        let huge = u64::MAX / 2 + 1;
+        let state = run_to_end(PricingRule::FirstPrice, &[(huge, 200), (huge, 200)]).await;
+        assert_eq!(state.winners.len(), 2);
+        assert_eq!(state.clearing_price, u64::MAX);
+
+        // Many bids of distinct sizes, whose exact frontier would hold millions of allocations
+        let bids: Vec<(u64, u64)> = (1..=200).map(|i| (i * 7 + i % 13, i)).collect();
+        let state = run_to_end(PricingRule::FirstPrice, &bids).await;
+        let allocated: u64 = state.winners.iter().map(|w| w.allocated_size).sum();
+        assert!(allocated <= 500);
+        assert!(state.clearing_price > 0);
+    }
+
+    /// Charges every bid its own amount, except that the second bid only pays 1, and also
+    /// returns winners that are duplicated, unknown, or do not fit.
+    struct CarelessRule;
+
+    impl AuctionRule for CarelessRule {
+        fn select_winners(&self, bids: &[Bid], _: u64, _: u64) -> Vec<AuctionWinner> {
+            let winner = |bid: &Bid, price: u64| AuctionWinner {
+                bidder_addr: bid.bidder_addr.clone(),
+                bid_hash: bid.hash(),
+                bid_amount: bid.effective_value(),
+                allocated_size: bid.blockspace_used(),
+                price,
+            };
+            let unknown = AuctionWinner {
+                bid_hash: "0xUnknownBid".to_string(),
+                ..winner(&bids[0], 1000)
+            };
+            vec![
+                winner(&bids[0], bids[0].effective_value()),
+                winner(&bids[0], bids[0].effective_value()),
+                unknown,
+                winner(&bids[1], 1),
+                winner(&bids[2], bids[2].effective_value()),
+                winner(&bids[3], bids[3].effective_value()),
+            ]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_winners_of_auction_rule_are_dropped() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender)
+            .with_auction_rule(Arc::new(CarelessRule));
+        let info = ended_auction_info().with_reserve_price(100);
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        let bids = [(1000, 200), (900, 100), (800, 400), (700, 200)]
+            .iter()
+            .enumerate()
+            .map(|(i, (amount, size))| {
+                bid(&auction_id, &format!("0xBidder{}", i + 1), *amount, *size)
+            })
+            .collect();
+        worker
+            .submit_bid_batch(auction_id, bids, Vec::new())
+            .await
+            .unwrap();
+        worker.process_auction().await.unwrap();
+
+        // 0xBidder2 pays less than the reserve, and 0xBidder3 does not fit next to 0xBidder1
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1", "0xBidder4"]);
+        assert_eq!(state.clearing_price, 1700);
+    }
+
+    #[tokio::test]
+    async fn test_bid_below_reserve_price_is_rejected() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let info = ended_auction_info().with_reserve_price(1000);
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        let result = worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder1", 999, 500))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AuctionError::BidBelowReservePrice {
+                bid_amount: 999,
+                reserve_price: 1000
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auction_without_bids_at_reserve_has_no_winner() {
+        let info = ended_auction_info().with_reserve_price(2000);
+        let state =
+            run_to_end_with_info(PricingRule::FirstPrice, info, &[(1000, 500), (1500, 500)]).await;
+
+        assert!(state.is_ended);
+        assert!(!state.reserve_met);
+        assert!(state.winners.is_empty());
+        assert!(state.bids.is_empty());
+        assert_eq!(state.clearing_price, 0);
+    }
+
+    #[tokio::test]
+    async fn test_second_price_is_raised_to_reserve_price() {
+        let info = ended_auction_info().with_reserve_price(1100);
+        let state =
+            run_to_end_with_info(PricingRule::SecondPrice, info, &[(1500, 500), (1200, 500)]).await;
+
+        assert!(state.reserve_met);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+        assert_eq!(state.clearing_price, 1200);
+
+        let info = ended_auction_info().with_reserve_price(1100);
+        let state = run_to_end_with_info(PricingRule::SecondPrice, info, &[(1500, 500)]).await;
+
+        assert_eq!(state.clearing_price, 1100);
+    }
+
+    #[tokio::test]
+    async fn test_equal_bids_are_resolved_by_earliest_arrival() {
+        let info = ended_auction_info();
+        let mut late = bid(&info.id, "0xBidder1", 1000, 500);
+        late.sequence = 1;
+        let mut early = bid(&info.id, "0xBidder2", 1000, 500);
+        early.sequence = 0;
+
+        let state = settle_bids(PricingRule::FirstPrice, info, vec![late, early]).await;
+
+        assert_eq!(
+            state.tie_break_rule,
+            TieBreakRule::EarliestArrivalThenBidHash
+        );
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+    }
+
+    #[tokio::test]
+    async fn test_direct_bids_are_stamped_by_worker() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder1", 1000, 500))
+            .await
+            .unwrap();
+        // A later bid cannot claim an earlier arrival to win the tie
+        let forged = Bid {
+            sequence: 0,
+            arrival_time: 1,
+            ..bid(&auction_id, "0xBidder2", 1000, 500)
+        };
+        worker.submit_bid(auction_id.clone(), forged).await.unwrap();
+        worker.process_auction().await.unwrap();
+
+        let state = worker.get_auction_state().await.unwrap();
+        let sequences: Vec<u64> = state.bids.iter().map(|b| b.sequence).collect();
+        assert_eq!(sequences, vec![0, 1]);
+        assert!(state.bids.iter().all(|b| b.arrival_time >= now));
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+    }
+
+    #[test]
+    fn test_bid_hash_does_not_depend_on_signature_spelling() {
+        let info = ended_auction_info();
+        let original = bid(&info.id, "0xBidder1", 1000, 500);
+
+        // The same signature without its prefix, in upper case, and with a raw recovery ID
+        let mut bytes = hex::decode(original.bidder_signature.trim_start_matches("0x")).unwrap();
+        bytes[64] -= 27;
+        let respelled = Bid {
+            bidder_signature: hex::encode_upper(bytes),
+            ..original.clone()
+        };
+        let verifier = SignatureScheme::default().verifier();
+        assert!(verifier.verify(
+            &respelled.bidder_addr,
+            &respelled.signing_hash(),
+            &respelled.bidder_signature
+        ));
+        assert_eq!(respelled.hash(), original.hash());
+
+        // Neither does a fresh signature over the same bid
+        assert_eq!(signed(respelled).hash(), original.hash());
+    }
+
+    #[tokio::test]
+    async fn test_equal_bids_with_same_arrival_are_resolved_by_bid_hash() {
+        let info = ended_auction_info();
+        let first = bid(&info.id, "0xBidder1", 1000, 500);
+        let second = bid(&info.id, "0xBidder2", 1000, 500);
+        let expected = if first.hash() < second.hash() {
+            "0xBidder1"
+        } else {
+            "0xBidder2"
+        };
+
+        // The outcome must not depend on submission order
+        let state = settle_bids(
+            PricingRule::FirstPrice,
+            info.clone(),
+            vec![first.clone(), second.clone()],
+        )
+        .await;
+        assert_eq!(winner_addrs(&state), vec![expected]);
+
+        let state = settle_bids(PricingRule::FirstPrice, info, vec![second, first]).await;
+        assert_eq!(winner_addrs(&state), vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn test_leading_bid_near_end_time_extends_auction() {
+        let (sender, mut receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            soft_close: Some(SoftCloseRule {
+                window_ms: 1000,
+                extension_ms: 2000,
+                max_extension_ms: 3000,
+            }),
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 500,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info.clone())
+            .await
+            .unwrap();
+
+        // Arrival times are stamped by `BidService`, whose bids reach the worker in batches
+        let arrived_at = |bid: Bid, arrival_time: u64| Bid {
+            arrival_time,
+            ..bid
+        };
+
+        // A new leader that arrived within the final window extends the end time from its arrival
+        let sniping_bid = arrived_at(
+            bid(&auction_id, "0xBidder1", 1000, 500),
+            info.end_time - 100,
+        );
+        worker
+            .submit_bid_batch(auction_id.clone(), vec![sniping_bid], Vec::new())
+            .await
+            .unwrap();
+        let extended_end_time = worker.get_auction_state().await.unwrap().end_time;
+        assert_eq!(extended_end_time, info.end_time + 1900);
+        assert!(matches!(
+            receiver.recv().await.unwrap().message_type,
+            WorkerMessageType::AuctionExtended(end_time) if end_time == extended_end_time
+        ));
+
+        // A bid that does not take the lead leaves the end time untouched
+        worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder2", 900, 500))
+            .await
+            .unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(state.end_time, extended_end_time);
+        assert!(receiver.try_recv().is_err());
+
+        // Extensions never go past the configured maximum
+        let late_bid = arrived_at(
+            bid(&auction_id, "0xBidder3", 1100, 500),
+            info.end_time + 1800,
+        );
+        worker
+            .submit_bid_batch(auction_id.clone(), vec![late_bid], Vec::new())
+            .await
+            .unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(state.end_time, info.end_time + 3000);
+        assert!(matches!(
+            receiver.recv().await.unwrap().message_type,
+            WorkerMessageType::AuctionExtended(end_time) if end_time == info.end_time + 3000
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unbounded_soft_close_saturates_end_time() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            soft_close: Some(SoftCloseRule {
+                window_ms: 1000,
+                extension_ms: u64::MAX,
+                max_extension_ms: u64::MAX,
+            }),
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 500,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info.clone())
+            .await
+            .unwrap();
+
+        let sniping_bid = Bid {
+            arrival_time: info.end_time - 100,
+            ..bid(&auction_id, "0xBidder1", 1000, 500)
+        };
+        worker
+            .submit_bid_batch(auction_id, vec![sniping_bid], Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(worker.get_auction_state().await.unwrap().end_time, u64::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_english_auction_requires_minimum_increment() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::English { min_increment: 100 },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder1", 1000, 10))
+            .await
+            .unwrap();
+        assert_eq!(worker.get_current_price().await.unwrap(), 1000);
+
+        let result = worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder2", 1050, 10))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AuctionError::BidTooLow {
+                current_price: 1000,
+                minimum_bid: 1100
+            })
+        ));
+
+        worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder2", 1100, 10))
+            .await
+            .unwrap();
+        assert_eq!(worker.get_current_price().await.unwrap(), 1100);
+
+        worker.process_auction().await.unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+        assert_eq!(state.winners[0].allocated_size, 500);
+        assert_eq!(state.clearing_price, 1100);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_bid_does_not_evict_under_cap() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::English { min_increment: 100 },
+            bid_limits: BidLimits {
+                max_bids_per_auction: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        for (bidder_addr, bid_amount) in [("0xBidder1", 1000), ("0xBidder2", 1100)] {
+            worker
+                .submit_bid(
+                    auction_id.clone(),
+                    bid(&auction_id, bidder_addr, bid_amount, 10),
+                )
+                .await
+                .unwrap();
+        }
+
+        // Outranks 0xBidder1 but misses the minimum increment, so nothing is evicted
+        let result = worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder3", 1150, 10))
+            .await;
+        assert!(matches!(result, Err(AuctionError::BidTooLow { .. })));
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(state.bids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sealed_bid_auction_hides_current_price() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let info = ended_auction_info();
+        worker.start_auction(info.id.clone(), info).await.unwrap();
+
+        assert!(matches!(
+            worker.get_current_price().await,
+            Err(AuctionError::SealedBidAuction)
+        ));
+    }
+
+    #[test]
+    fn test_dutch_price_decays_in_steps_to_floor() {
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            10_000,
+            20_000,
+            "0xSellerSignature".to_string(),
+        );
+
+        assert_eq!(dutch_price(&info, 5000, 1000, 1000, 9_000), 5000);
+        assert_eq!(dutch_price(&info, 5000, 1000, 1000, 10_000), 5000);
+        assert_eq!(dutch_price(&info, 5000, 1000, 1000, 12_500), 4200);
+        assert_eq!(dutch_price(&info, 5000, 1000, 0, 12_500), 4000);
+        assert_eq!(dutch_price(&info, 5000, 1000, 1000, 20_000), 1000);
+        assert_eq!(dutch_price(&info, 5000, 1000, 1000, 25_000), 1000);
+    }
+
+    #[tokio::test]
+    async fn test_dutch_auction_closes_at_first_bid_meeting_price() {
+        let (sender, mut receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::Dutch {
+                start_price: 10_000,
+                floor_price: 1000,
+                step_ms: 0,
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        let result = worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder1", 5000, 10))
+            .await;
+        assert!(matches!(result, Err(AuctionError::BidTooLow { .. })));
+
+        worker
+            .submit_bid(
+                auction_id.clone(),
+                bid(&auction_id, "0xBidder2", 10_000, 10),
+            )
+            .await
+            .unwrap();
+
+        // The manager is notified without waiting for the end time
+        assert!(matches!(
+            receiver.recv().await.unwrap().message_type,
+            WorkerMessageType::AuctionClosed
+        ));
+
+        let state = worker.get_auction_state().await.unwrap();
+        assert!(state.is_ended);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+        assert!(state.clearing_price > 5000 && state.clearing_price <= 10_000);
+
+        let result = worker
+            .submit_bid(
+                auction_id.clone(),
+                bid(&auction_id, "0xBidder3", 10_000, 10),
+            )
+            .await;
+        assert!(matches!(result, Err(AuctionError::AuctionEnded)));
+    }
+
+    #[tokio::test]
+    async fn test_bids_and_commitments_must_be_signed_by_bidder() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        // A bid signed by someone else is refused, alone or in a batch
+        let mut forged = bid(&auction_id, "0xBidder1", 1000, 500);
+        forged.bidder_signature = key_of("0xBidder2").sign(&forged.signing_hash()).unwrap();
+        let result = worker.submit_bid(auction_id.clone(), forged.clone()).await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+        worker
+            .submit_bid_batch(auction_id.clone(), vec![forged], Vec::new())
+            .await
+            .unwrap();
+        assert!(worker.get_auction_state().await.unwrap().bids.is_empty());
+
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::CommitReveal {
+                reveal_window_ms: 0,
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        let committed = bid(&auction_id, "0xBidder1", 1000, 500);
+        let mut commitment = BidCommitment {
+            chain_id: 1,
+            auction_id: auction_id.clone(),
+            bidder_addr: committed.bidder_addr.clone(),
+            commitment: committed.commitment("salt"),
+            bidder_signature: String::new(),
+        };
+        commitment.bidder_signature = key_of("0xBidder2")
+            .sign(&commitment.signing_hash())
+            .unwrap();
+        let result = worker.submit_commitment(auction_id, commitment).await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+    }
+
+    #[tokio::test]
+    async fn test_unbounded_reveal_window_stays_open() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::CommitReveal {
+                reveal_window_ms: u64::MAX,
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker.start_auction(auction_id, info).await.unwrap();
+
+        // The end time opens the reveal window, whose end saturates instead of overflowing
+        worker.process_auction().await.unwrap();
+        worker.process_auction().await.unwrap();
+        assert_eq!(
+            worker.get_auction_state().await.unwrap().phase,
+            AuctionPhase::Reveal
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_reveal_forfeits_unrevealed_commitments() {
+        let (sender, mut receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::CommitReveal {
+                reveal_window_ms: 0,
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        let revealed = bid(&auction_id, "0xBidder1", 1000, 500);
+        let hidden = bid(&auction_id, "0xBidder2", 1500, 500);
+        for (b, salt) in [(&revealed, "salt1"), (&hidden, "salt2")] {
+            let mut commitment = BidCommitment {
+                chain_id: 1,
+                auction_id: auction_id.clone(),
+                bidder_addr: b.bidder_addr.clone(),
+                commitment: b.commitment(salt),
+                bidder_signature: String::new(),
+            };
+            commitment.bidder_signature = key_of(name_of(&b.bidder_addr))
+                .sign(&commitment.signing_hash())
+                .unwrap();
+            worker
+                .submit_commitment(auction_id.clone(), commitment)
+                .await
+                .unwrap();
+        }
+
+        // Plaintext bids are refused and nothing can be revealed during the bidding phase
+        let result = worker
+            .submit_bid(auction_id.clone(), revealed.clone())
+            .await;
+        assert!(matches!(result, Err(AuctionError::CommitmentRequired)));
+        let result = worker
+            .reveal_bid(auction_id.clone(), revealed.clone(), "salt1".to_string())
+            .await;
+        assert!(matches!(result, Err(AuctionError::NotInRevealPhase)));
+
+        // The end time opens the reveal window
+        worker.process_auction().await.unwrap();
+        assert!(matches!(
+            receiver.recv().await.unwrap().message_type,
+            WorkerMessageType::RevealStarted
+        ));
+        assert_eq!(
+            worker.get_auction_state().await.unwrap().phase,
+            AuctionPhase::Reveal
+        );
+
+        let result = worker
+            .reveal_bid(auction_id.clone(), revealed.clone(), "wrong".to_string())
+            .await;
+        assert!(matches!(result, Err(AuctionError::InvalidReveal)));
+        worker
+            .reveal_bid(auction_id.clone(), revealed, "salt1".to_string())
+            .await
+            .unwrap();
+
+        // Closing the reveal window forfeits the higher but unrevealed commitment
+        worker.process_auction().await.unwrap();
+        assert!(matches!(
+            receiver.recv().await.unwrap().message_type,
+            WorkerMessageType::AuctionClosed
+        ));
+        assert_eq!(
+            worker.get_auction_state().await.unwrap().phase,
+            AuctionPhase::Settling
+        );
+        let state = worker.finalize_auction(auction_id.clone()).await.unwrap();
+        assert_eq!(state.phase, AuctionPhase::Ended);
+        assert!(state.is_ended);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+        assert_eq!(state.forfeited_commitments.len(), 1);
+        assert_eq!(
+            state.forfeited_commitments[0].bidder_addr,
+            addr("0xBidder2")
+        );
+        assert!(state.commitments.is_empty());
+
+        // Both commitments and the revealed bid are committed to, and so is how each left
+        assert_eq!(state.bid_log.len(), 3);
+        let reasons: Vec<RemovalReason> = state
+            .bid_removals
+            .iter()
+            .map(|removal| removal.reason)
+            .collect();
+        assert_eq!(reasons, [RemovalReason::Opened, RemovalReason::Forfeited]);
+        assert_eq!(
+            state.bid_removals[1].submission_hash,
+            state.forfeited_commitments[0].hash()
+        );
+        assert!(state.bid_tree.is_some() && state.removal_tree.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_bids_are_decrypted_at_auction_close() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::Encrypted,
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        let public_key = worker.get_encryption_public_key().await.unwrap();
+
+        // Plaintext bids are refused
+        let plain = bid(&auction_id, "0xBidder1", 1000, 500);
+        let result = worker.submit_bid(auction_id.clone(), plain.clone()).await;
+        assert!(matches!(result, Err(AuctionError::EncryptedBidRequired)));
+
+        let low = encrypt_bid(&plain, &public_key).unwrap();
+        let high = encrypt_bid(&bid(&auction_id, "0xBidder2", 1500, 500), &public_key).unwrap();
+        // A bid encrypted to another key cannot be decrypted and is dropped at close
+        let other_key = AuctionKeypair::generate().public_key();
+        let undecryptable =
+            encrypt_bid(&bid(&auction_id, "0xBidder3", 2000, 500), &other_key).unwrap();
+        for encrypted_bid in [low, high, undecryptable] {
+            worker
+                .submit_encrypted_bid(auction_id.clone(), encrypted_bid)
+                .await
+                .unwrap();
+        }
+
+        // Nothing is readable before the end
+        let state = worker.get_auction_state().await.unwrap();
+        assert!(state.bids.is_empty());
+        assert_eq!(state.encrypted_bids.len(), 3);
+
+        worker.process_auction().await.unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert!(state.is_ended);
+        assert!(state.encrypted_bids.is_empty());
+        assert_eq!(state.bids.len(), 2);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+        assert_eq!(state.bids[0].sequence, 1);
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_bid_sequences_are_not_reused_after_cancellation() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::Encrypted,
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        let public_key = worker.get_encryption_public_key().await.unwrap();
+
+        for name in ["0xBidder1", "0xBidder2"] {
+            let encrypted_bid =
+                encrypt_bid(&bid(&auction_id, name, 1000, 500), &public_key).unwrap();
+            worker
+                .submit_encrypted_bid(auction_id.clone(), encrypted_bid)
+                .await
+                .unwrap();
+        }
+
+        let mut cancellation = BidCancellation {
+            chain_id: 1,
+            auction_id: auction_id.clone(),
+            bidder_addr: addr("0xBidder1"),
+            nonce: 0,
+            bidder_signature: String::new(),
+        };
+        cancellation.bidder_signature = key_of("0xBidder1")
+            .sign(&cancellation.signing_hash())
+            .unwrap();
+        worker
+            .cancel_bid(auction_id.clone(), cancellation)
+            .await
+            .unwrap();
+
+        // The next encrypted bid does not take the number of the remaining one
+        let encrypted_bid =
+            encrypt_bid(&bid(&auction_id, "0xBidder3", 1000, 500), &public_key).unwrap();
+        worker
+            .submit_encrypted_bid(auction_id.clone(), encrypted_bid)
+            .await
+            .unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        let sequences: Vec<u64> = state.encrypted_bids.iter().map(|b| b.sequence).collect();
+        assert_eq!(sequences, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_newer_bid_replaces_and_cancellation_withdraws() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        let bidder1_key = key_of("0xBidder1");
+        let bidder1_addr = bidder1_key.address();
+        let first = signed(Bid {
+            nonce: 1,
+            ..bid(&auction_id, "0xBidder1", 2000, 500)
+        });
+        let replacement = signed(Bid {
+            nonce: 2,
+            tx_list: vec![Tx {
+                tx_data: "0xBidder1ReplacementTx".to_string(),
+                gas_limit: 500,
+            }],
+            ..bid(&auction_id, "0xBidder1", 1200, 500)
+        });
+        let other = signed(Bid {
+            nonce: 1,
+            ..bid(&auction_id, "0xBidder2", 1000, 500)
+        });
+        worker
+            .submit_bid_batch(
+                auction_id.clone(),
+                vec![first.clone(), replacement, other],
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        // The stale bid cannot come back
+        let result = worker.submit_bid(auction_id.clone(), first).await;
+        assert!(matches!(
+            result,
+            Err(AuctionError::StaleBidNonce {
+                nonce: 1,
+                current_nonce: 2
+            })
+        ));
+
+        // The winning tx list comes from the replacement, not from the first bid
+        worker.process_auction().await.unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(state.bids.len(), 2);
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+        let tob = worker.get_latest_tob().await.unwrap();
+        assert_eq!(tob[0].tx_data, "0xBidder1ReplacementTx");
+
+        let mut cancellation = BidCancellation {
+            chain_id: 1,
+            auction_id: auction_id.clone(),
+            bidder_addr: bidder1_addr.clone(),
+            nonce: 2,
+            bidder_signature: String::new(),
+        };
+
+        // Only the bidder can withdraw their bid
+        cancellation.bidder_signature = Secp256k1Key::generate()
+            .sign(&cancellation.signing_hash())
+            .unwrap();
+        let result = worker
+            .cancel_bid(auction_id.clone(), cancellation.clone())
+            .await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+
+        cancellation.bidder_signature = bidder1_key.sign(&cancellation.signing_hash()).unwrap();
+        worker
+            .cancel_bid(auction_id.clone(), cancellation.clone())
+            .await
+            .unwrap();
+        let result = worker.cancel_bid(auction_id.clone(), cancellation).await;
+        assert!(matches!(result, Err(AuctionError::BidNotFound(_))));
+
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2"]);
+    }
+
+    #[tokio::test]
+    async fn test_auction_bid_cap_keeps_best_bids() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            bid_limits: BidLimits {
+                max_bids_per_bidder: None,
+                max_bids_per_auction: Some(2),
+            },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        worker
+            .submit_bid_batch(
+                auction_id.clone(),
+                vec![
+                    bid(&auction_id, "0xBidder1", 1000, 100),
+                    bid(&auction_id, "0xBidder2", 2000, 100),
+                ],
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        // A worse bid is rejected, a better one evicts the worst
+        let result = worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder3", 500, 100))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AuctionError::AuctionBidLimitReached { limit: 2 })
+        ));
+        worker
+            .submit_bid(auction_id.clone(), bid(&auction_id, "0xBidder4", 1500, 100))
+            .await
+            .unwrap();
+
+        // A bidder replacing their own bid is not affected by the cap
+        let replacement = signed(Bid {
+            nonce: 1,
+            ..bid(&auction_id, "0xBidder4", 1600, 100)
+        });
+        worker
+            .submit_bid(auction_id.clone(), replacement)
+            .await
+            .unwrap();
+
+        worker.process_auction().await.unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(winner_addrs(&state), vec!["0xBidder2", "0xBidder4"]);
+    }
+
+    #[tokio::test]
+    async fn test_package_leg_takes_top_of_block() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+        worker
+            .submit_bid_batch(
+                auction_id.clone(),
+                vec![
+                    bid(&auction_id, "0xBidder1", 1000, 400),
+                    bid(&auction_id, "0xBidder2", 300, 200),
+                ],
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        let leg = bid(&auction_id, "0xPackage", 800, 300);
+        worker
+            .assign_package_leg(auction_id.clone(), leg)
+            .await
+            .unwrap();
+
+        // Only 200 of the blockspace is left for the single-block bids
+        worker.process_auction().await.unwrap();
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(winner_addrs(&state), vec!["0xPackage", "0xBidder2"]);
+        assert_eq!(state.clearing_price, 1100);
+        let tob = worker.get_latest_tob().await.unwrap();
+        assert_eq!(tob[0].tx_data, "0xPackageTx");
+    }
+
+    #[tokio::test]
+    async fn test_block_ordering_fills_blockspace_after_winners() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let chain_info = ChainInfo {
+            auction_mode: AuctionMode::English { min_increment: 0 },
+            ..Default::default()
+        };
+        let worker = AuctionWorker::new(1, &chain_info, sender);
+        let info = ended_auction_info();
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        worker
+            .submit_bid_batch(
+                auction_id.clone(),
+                vec![
+                    bid(&auction_id, "0xBidder1", 100, 300),
+                    bid(&auction_id, "0xBidder2", 200, 200),
+                    bid(&auction_id, "0xBidder3", 300, 100),
+                ],
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+        worker.process_auction().await.unwrap();
+
+        // The winning bundle uses 100 of 500, and the rest is filled by value per gas
+        let ordering = worker.get_block_ordering().await.unwrap();
+        let segment: Vec<(usize, &str, bool)> = ordering
+            .iter()
+            .map(|ordered| {
+                (
+                    ordered.position,
+                    name_of(&ordered.bidder_addr),
+                    ordered.is_winner,
+                )
+            })
+            .collect();
+        assert_eq!(
+            segment,
+            vec![(0, "0xBidder3", true), (1, "0xBidder2", false)]
+        );
+        assert_eq!(ordering[1].tx.tx_data, "0xBidder2Tx");
+    }
+
+    #[tokio::test]
+    async fn test_bid_consumes_at_least_its_transactions_gas() {
+        let info = ended_auction_info();
+        let understated = signed(Bid {
+            blockspace_size: 100,
+            tx_list: vec![Tx {
+                tx_data: "0xBidder1Tx".to_string(),
+                gas_limit: 400,
+            }],
+            ..bid(&info.id, "0xBidder1", 1000, 100)
+        });
+        assert_eq!(understated.blockspace_used(), 400);
+
+        // Gas limits that overflow a u64 are rejected rather than wrapped
+        let overflowing = signed(Bid {
+            tx_list: vec![
+                Tx {
+                    tx_data: "0xBidder2Tx1".to_string(),
+                    gas_limit: u64::MAX,
+                },
+                Tx {
+                    tx_data: "0xBidder2Tx2".to_string(),
+                    gas_limit: 1,
+                },
+            ],
+            price_per_gas: Some(1),
+            ..bid(&info.id, "0xBidder2", 1000, 100)
+        });
+        assert_eq!(overflowing.gas_used(), None);
+
+        // 0xBidder1's transactions do not fit next to 0xBidder3's, despite its declared size
+        let state = settle_bids(
+            PricingRule::FirstPrice,
+            info.clone(),
+            vec![
+                understated,
+                overflowing,
+                bid(&info.id, "0xBidder3", 900, 400),
+            ],
+        )
+        .await;
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1"]);
+        assert_eq!(state.bids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_per_gas_bids_compete_by_effective_value() {
+        let info = ended_auction_info();
+        let per_gas_bid = signed(Bid {
+            price_per_gas: Some(3),
+            tx_list: vec![
+                Tx {
+                    tx_data: "0xBidder1Tx1".to_string(),
+                    gas_limit: 100,
+                },
+                Tx {
+                    tx_data: "0xBidder1Tx2".to_string(),
+                    gas_limit: 100,
+                },
+            ],
+            // Ignored for per-gas bids
+            bid_amount: 1,
+            blockspace_size: 1,
+            ..bid(&info.id, "0xBidder1", 0, 0)
+        });
+        assert_eq!(per_gas_bid.effective_value(), 600);
+        assert_eq!(per_gas_bid.blockspace_used(), 200);
+
+        let bids = vec![
+            per_gas_bid,
+            bid(&info.id, "0xBidder2", 700, 400),
+            bid(&info.id, "0xBidder3", 350, 300),
+        ];
+        let state = settle_bids(PricingRule::FirstPrice, info, bids).await;
+
+        assert_eq!(winner_addrs(&state), vec!["0xBidder1", "0xBidder3"]);
+        assert_eq!(state.winners[0].bid_amount, 600);
+        assert_eq!(state.winners[0].allocated_size, 200);
+        assert_eq!(state.clearing_price, 950);
+    }
+
+    #[tokio::test]
+    async fn test_zero_price_per_gas_is_rejected() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        );
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        // A copy at a zero price per gas carries a valid signature of the original
+        let original = bid(&auction_id, "0xBidder1", 1000, 500);
+        let copy = Bid {
+            price_per_gas: Some(0),
+            ..original.clone()
+        };
+        assert_eq!(copy.signing_hash(), original.signing_hash());
+        let result = worker.submit_bid(auction_id.clone(), copy).await;
+        assert!(matches!(result, Err(AuctionError::ZeroPricePerGas)));
+        worker.submit_bid(auction_id, original).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receipted_bids_are_logged_when_dropped() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let worker = AuctionWorker::new(1, &ChainInfo::default(), sender);
+        let now = current_unix_ms();
+        let info = AuctionInfo::new(
+            1,
+            100,
+            "0xTestSeller".to_string(),
+            500,
+            now - 1000,
+            now + 60_000,
+            "0xSellerSignature".to_string(),
+        )
+        .with_reserve_price(500);
+        let auction_id = info.id.clone();
+        worker
+            .start_auction(auction_id.clone(), info)
+            .await
+            .unwrap();
+
+        // A bid evicted from BidService's buffer, and a flushed bid below the reserve price
+        let evicted = bid(&auction_id, "0xBidder1", 600, 100);
+        let below_reserve = bid(&auction_id, "0xBidder2", 100, 100);
+        let accepted = bid(&auction_id, "0xBidder3", 1000, 100);
+        worker
+            .submit_bid_batch(
+                auction_id.clone(),
+                vec![below_reserve.clone(), accepted],
+                vec![BidRemoval {
+                    submission_hash: evicted.hash(),
+                    reason: RemovalReason::Evicted,
+                }],
+            )
+            .await
+            .unwrap();
+
+        let state = worker.get_auction_state().await.unwrap();
+        assert_eq!(state.bids.len(), 1);
+        assert_eq!(state.bid_log.len(), 3);
+        let removals: Vec<(String, RemovalReason)> = state
+            .bid_removals
+            .iter()
+            .map(|removal| (removal.submission_hash.clone(), removal.reason))
+            .collect();
+        assert_eq!(
+            removals,
+            [
+                (evicted.hash(), RemovalReason::Evicted),
+                (below_reserve.hash(), RemovalReason::Rejected),
+            ]
+        );
     }
 }
@@ -1,27 +1,417 @@
+use std::{cmp::Ordering, collections::HashMap};
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
-use crate::utils::{errors::DatabaseError, helpers::compute_hash};
+use crate::utils::{
+    eip712,
+    errors::{DatabaseError, SignatureError},
+    helpers::{compute_framed_hash, compute_hash, keccak256},
+    merkle::{self, MerkleProof, MerkleTree},
+    signing::{self, Ed25519Verifier, Secp256k1Key, Secp256k1Verifier, SignatureVerifier},
+};
 
 /// Represents a transaction submitted by a bidder (mock).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Tx {
     pub tx_data: String,
+    /// The maximum amount of gas the transaction may consume.
+    pub gas_limit: u64,
 }
 
-/// Represents a bid submitted by a buyer, including bidder address, amount, signature, and transaction list.
-#[derive(Debug, Clone)]
+/// Represents a bid submitted by a buyer, including bidder address, amount, consumed blockspace, signature, and transaction list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Bid {
     pub chain_id: ChainId,
     pub auction_id: AuctionId,
     pub bidder_addr: String,
     pub bid_amount: u64,
+    /// The amount of the auction's blockspace this bid reserves. The bid consumes at least the
+    /// gas of its `tx_list` (see `Bid::blockspace_used`).
+    pub blockspace_size: u64,
     pub bidder_signature: String,
     pub tx_list: Vec<Tx>,
+    /// Optional price per unit of gas. When set, the bid pays this price for every unit of gas
+    /// its `tx_list` consumes, and `bid_amount` and `blockspace_size` are ignored. A zero price
+    /// is rejected, since it signs the same as a bid without a price per gas.
+    pub price_per_gas: Option<u64>,
+    /// Bidder-chosen nonce. A bid replaces the same bidder's earlier bid in the auction
+    /// only if its nonce is higher.
+    pub nonce: u64,
+    /// Per-auction arrival sequence number, assigned by `BidService::store_bid`, or by the
+    /// worker for a bid submitted otherwise. The value set by the client is ignored.
+    pub sequence: u64,
+    /// Server arrival timestamp in Unix milliseconds, assigned along with `sequence`.
+    pub arrival_time: u64,
+}
+
+impl Bid {
+    /// Returns the total gas limit of the bid's transactions, or `None` if it overflows a `u64`.
+    /// Such bids are rejected on acceptance.
+    pub fn gas_used(&self) -> Option<u64> {
+        self.tx_list
+            .iter()
+            .try_fold(0u64, |total, tx| total.checked_add(tx.gas_limit))
+    }
+
+    /// Returns the amount of blockspace the bid consumes: the gas of its `tx_list`. A fixed-amount
+    /// bid may declare a larger `blockspace_size` to reserve more room than its transactions need,
+    /// but never less.
+    pub fn blockspace_used(&self) -> u64 {
+        let gas_used = self.gas_used().unwrap_or(u64::MAX);
+        match self.price_per_gas {
+            Some(_) => gas_used,
+            None => gas_used.max(self.blockspace_size),
+        }
+    }
+
+    /// Returns the total amount the bid pays, which winner selection and ordering compare:
+    /// `price_per_gas` times the gas of its `tx_list` for a per-gas bid, and `bid_amount` otherwise.
+    pub fn effective_value(&self) -> u64 {
+        match self.price_per_gas {
+            Some(price_per_gas) => price_per_gas.saturating_mul(self.gas_used().unwrap_or(0)),
+            None => self.bid_amount,
+        }
+    }
+
+    /// Computes the EIP-712 digest the bidder signs (see `utils::eip712`). It covers every field
+    /// of the bid that is set by the bidder, except the signature itself.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        eip712::bid_digest(self)
+    }
+
+    /// Computes the commitment a bidder submits in a commit-reveal auction before revealing this bid.
+    pub fn commitment(&self, salt: &str) -> String {
+        compute_hash(&[self.hash().as_bytes(), salt.as_bytes()])
+    }
+
+    /// Computes the hash identifying the bid, e.g., in the auction's bid log.
+    /// It is derived from `signing_hash`, which covers every field set by the bidder, rather than
+    /// from the signature string: a signature can be spelled in many ways, and a bidder can make
+    /// any number of signatures over the same bid, so neither must change the bid's identity or
+    /// its place in hash tie-breaks. Server-assigned fields (`sequence`, `arrival_time`) are not
+    /// part of the hash.
+    pub fn hash(&self) -> String {
+        compute_framed_hash(&[b"bid", &self.signing_hash()])
+    }
+}
+
+/// A hash commitment to a bid, submitted during the bidding phase of a commit-reveal auction.
+/// `commitment` must equal `Bid::commitment(salt)` of the bid revealed later.
+#[derive(Debug, Clone)]
+pub struct BidCommitment {
+    pub chain_id: ChainId,
+    pub auction_id: AuctionId,
+    pub bidder_addr: String,
+    pub commitment: String,
+    pub bidder_signature: String,
+}
+
+impl BidCommitment {
+    /// Computes the hash identifying the commitment in the auction's bid log.
+    pub fn hash(&self) -> String {
+        compute_framed_hash(&[
+            b"bid-commitment",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.bidder_addr.as_bytes(),
+            self.commitment.as_bytes(),
+            self.bidder_signature.as_bytes(),
+        ])
+    }
+
+    /// Computes the message hash the bidder signs.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&[
+            b"commit-bid",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.bidder_addr.as_bytes(),
+            self.commitment.as_bytes(),
+        ])
+    }
+}
+
+/// An all-or-nothing bid on the top of several consecutive blocks of one chain.
+/// It holds one leg per auction, in block order, and either wins every leg or none.
+/// Each leg pays its own `bid_amount` and is allocated its own `blockspace_size`.
+#[derive(Debug, Clone, Default)]
+pub struct PackageBid {
+    pub chain_id: ChainId,
+    pub bidder_addr: String,
+    pub legs: Vec<Bid>,
+    pub bidder_signature: String,
+}
+
+impl PackageBid {
+    /// Returns the total amount the package pays across all of its legs, or `None` if it
+    /// overflows a `u64`. Such packages are rejected on submission.
+    pub fn total_amount(&self) -> Option<u64> {
+        self.legs
+            .iter()
+            .try_fold(0u64, |total, leg| total.checked_add(leg.effective_value()))
+    }
+
+    /// Returns the leg of the package in the given auction, if any.
+    pub fn leg_for(&self, auction_id: &AuctionId) -> Option<&Bid> {
+        self.legs.iter().find(|leg| &leg.auction_id == auction_id)
+    }
+
+    /// Computes the message hash the bidder signs, covering every leg.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let chain_id = self.chain_id.to_be_bytes();
+        let leg_hashes: Vec<[u8; 32]> = self.legs.iter().map(Bid::signing_hash).collect();
+
+        let mut inputs: Vec<&[u8]> = vec![&chain_id, self.bidder_addr.as_bytes()];
+        inputs.extend(leg_hashes.iter().map(|leg_hash| leg_hash.as_slice()));
+        keccak256(&inputs)
+    }
+}
+
+/// An all-or-nothing bid on the ongoing auctions of two or more chains, e.g., a cross-rollup
+/// arbitrage bundle. Each leg competes as an ordinary bid in its chain's auction, but the bid
+/// only counts if every leg wins; otherwise the winning legs are withdrawn after the fact.
+#[derive(Debug, Clone, Default)]
+pub struct CrossChainBid {
+    pub bidder_addr: String,
+    pub legs: Vec<Bid>,
+    pub bidder_signature: String,
+}
+
+impl CrossChainBid {
+    /// Computes the message hash the bidder signs, covering every leg.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let leg_hashes: Vec<[u8; 32]> = self.legs.iter().map(Bid::signing_hash).collect();
+
+        let mut inputs: Vec<&[u8]> = vec![self.bidder_addr.as_bytes()];
+        inputs.extend(leg_hashes.iter().map(|leg_hash| leg_hash.as_slice()));
+        keccak256(&inputs)
+    }
+}
+
+/// A signed request by a bidder to withdraw their bid from an auction before its end time.
+/// Any bid of the bidder with a nonce up to and including `nonce` is withdrawn, and later
+/// bids must use a higher nonce.
+#[derive(Debug, Clone)]
+pub struct BidCancellation {
+    pub chain_id: ChainId,
+    pub auction_id: AuctionId,
+    pub bidder_addr: String,
+    pub nonce: u64,
+    pub bidder_signature: String,
+}
+
+impl BidCancellation {
+    /// Computes the message hash the bidder signs.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&[
+            b"cancel-bid",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.bidder_addr.as_bytes(),
+            &self.nonce.to_be_bytes(),
+        ])
+    }
+}
+
+/// A bid encrypted to the public key of an auction (see `utils::encryption::encrypt_bid`).
+/// Only the routing fields and the bidder address are visible until the auction ends.
+#[derive(Debug, Clone, Default)]
+pub struct EncryptedBid {
+    pub chain_id: ChainId,
+    pub auction_id: AuctionId,
+    pub bidder_addr: String,
+    /// Hex-encoded ephemeral X25519 public key of the bidder.
+    pub ephemeral_public_key: String,
+    /// Hex-encoded ChaCha20-Poly1305 nonce.
+    pub nonce: String,
+    /// Hex-encoded ciphertext of the serialized `Bid`.
+    pub ciphertext: String,
+    /// Per-auction arrival sequence number, assigned by the worker on receipt.
+    pub sequence: u64,
+    /// Arrival timestamp in Unix milliseconds, assigned by the worker on receipt.
+    pub arrival_time: u64,
+}
+
+impl EncryptedBid {
+    /// Computes the hash identifying the encrypted bid in the auction's bid log.
+    /// Server-assigned fields (`sequence`, `arrival_time`) are not part of the hash.
+    pub fn hash(&self) -> String {
+        compute_framed_hash(&[
+            b"encrypted-bid",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.bidder_addr.as_bytes(),
+            self.ephemeral_public_key.as_bytes(),
+            self.nonce.as_bytes(),
+            self.ciphertext.as_bytes(),
+        ])
+    }
+}
+
+/// The rule used to order bids with equal amounts. It is recorded in the auction outcome
+/// so that any bidder can reproduce the ordering.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreakRule {
+    /// Among equal amounts, the bid that arrived first (lowest `sequence`, then lowest
+    /// `arrival_time`) ranks higher. Bids that arrived at the same point are ordered by the
+    /// lexicographically smallest `Bid::hash`.
+    #[default]
+    EarliestArrivalThenBidHash,
+}
+
+impl TieBreakRule {
+    /// Compares two bids so that the better bid is ordered first: a higher effective value
+    /// (see `Bid::effective_value`) wins, and equal values are resolved by this rule.
+    pub fn compare(&self, a: &Bid, b: &Bid) -> Ordering {
+        match self {
+            TieBreakRule::EarliestArrivalThenBidHash => b
+                .effective_value()
+                .cmp(&a.effective_value())
+                .then_with(|| a.sequence.cmp(&b.sequence))
+                .then_with(|| a.arrival_time.cmp(&b.arrival_time))
+                .then_with(|| a.hash().cmp(&b.hash())),
+        }
+    }
 }
 
+/// Represents the configuration of a registered chain.
+#[derive(Debug, Clone, Default)]
 pub struct ChainInfo {
     pub gas_limit: u64,
     pub registered_sellers: Vec<String>,
+    pub pricing_rule: PricingRule,
+    /// Optional anti-sniping rule. `None` means auctions always close at `end_time`.
+    pub soft_close: Option<SoftCloseRule>,
+    /// How the chain's auctions take bids and find their winner. Defaults to a sealed-bid auction.
+    pub auction_mode: AuctionMode,
+    pub bid_limits: BidLimits,
+    pub ordering_rule: OrderingRule,
+    pub signature_scheme: SignatureScheme,
+}
+
+/// The kind of account whose signatures are accepted on a chain, for bids as well as for the
+/// auctions sold by its sellers. Every scheme signs the same message hashes (e.g.,
+/// `Bid::signing_hash`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignatureScheme {
+    /// Ethereum-style accounts (see `Secp256k1Verifier`).
+    #[default]
+    Secp256k1,
+    /// Accounts identified by their Ed25519 public key (see `Ed25519Verifier`).
+    Ed25519,
+}
+
+impl SignatureScheme {
+    /// Returns the `SignatureVerifier` of the scheme.
+    pub fn verifier(&self) -> &'static dyn SignatureVerifier {
+        match self {
+            SignatureScheme::Secp256k1 => &Secp256k1Verifier,
+            SignatureScheme::Ed25519 => &Ed25519Verifier,
+        }
+    }
+}
+
+/// The declared rule that orders the non-winning bids filling the rest of the sold blockspace
+/// (see `AuctionManager::request_block_ordering`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrderingRule {
+    /// Highest effective value per unit of blockspace used (i.e., per gas) first.
+    #[default]
+    ValuePerGas,
+    /// Highest effective value first.
+    BidAmount,
+}
+
+impl OrderingRule {
+    /// Compares two bids so that the bid to include first is ordered first.
+    /// Bids that are equal under this rule are ordered by `tie_break_rule`.
+    pub fn compare(&self, a: &Bid, b: &Bid, tie_break_rule: TieBreakRule) -> Ordering {
+        match self {
+            // Cross-multiplied so that no precision is lost
+            OrderingRule::ValuePerGas => {
+                let a_value = a.effective_value() as u128 * b.blockspace_used() as u128;
+                let b_value = b.effective_value() as u128 * a.blockspace_used() as u128;
+                b_value.cmp(&a_value)
+            }
+            OrderingRule::BidAmount => b.effective_value().cmp(&a.effective_value()),
+        }
+        .then_with(|| tie_break_rule.compare(a, b))
+    }
+}
+
+/// A transaction in the ordered block segment sold by an auction, with its source bid.
+#[derive(Debug, Clone)]
+pub struct OrderedTx {
+    /// Zero-based position of the transaction within the sold segment.
+    pub position: usize,
+    pub tx: Tx,
+    pub bidder_addr: String,
+    /// The hash of the bid the transaction comes from (see `Bid::hash`).
+    pub bid_hash: String,
+    /// Whether the source bid is one of the auction's winners rather than a filler bid.
+    pub is_winner: bool,
+}
+
+/// Determines how bids are submitted and how the winner is found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuctionMode {
+    /// Bids are hidden until the auction ends, and the blockspace is allocated by the chain's `PricingRule`.
+    #[default]
+    SealedBid,
+    /// Open ascending (English) auction: the current highest bid is public, every new bid must
+    /// beat it by at least `min_increment`, and the highest bid wins the whole blockspace at its own price.
+    English { min_increment: u64 },
+    /// Descending-price (Dutch) auction: the price decays from `start_price` at `start_time` to
+    /// `floor_price` at `end_time` in steps of `step_ms`. The first bid at or above the current
+    /// price wins the whole blockspace at that price and closes the auction immediately.
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        step_ms: u64,
+    },
+    /// Commit-reveal sealed bidding: until `end_time` bidders only submit a `BidCommitment`.
+    /// During the following `reveal_window_ms` they reveal the bid and salt, and commitments
+    /// that are never revealed are forfeited. The blockspace is then allocated as in `SealedBid`.
+    CommitReveal { reveal_window_ms: u64 },
+    /// Encrypted sealed bidding: a fresh keypair is generated for every auction, and bidders
+    /// submit `EncryptedBid`s to its public key. The whole batch is decrypted only when the
+    /// auction ends, and the blockspace is then allocated as in `SealedBid`.
+    Encrypted,
+}
+
+/// Determines the price the winner of an auction is charged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PricingRule {
+    /// The winner pays its own bid amount.
+    #[default]
+    FirstPrice,
+    /// Sealed-bid second-price (Vickrey): the winner pays the runner-up's bid amount,
+    /// or zero if there is no runner-up. With several winners this generalizes to VCG pricing,
+    /// where each winner pays the revenue its allocation displaces from the other bidders.
+    /// Past the allocation search's frontier cap the allocations are approximate, and a price is
+    /// then capped at the winner's own bid.
+    SecondPrice,
+}
+
+/// Anti-sniping rule: a new leading bid that arrives within the final `window_ms` before the
+/// end time pushes the end time to `extension_ms` after the bid, but never more than
+/// `max_extension_ms` past the auction's original `end_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftCloseRule {
+    pub window_ms: u64,
+    pub extension_ms: u64,
+    pub max_extension_ms: u64,
+}
+
+/// Caps on the number of bids kept per auction, enforced by both `BidService` (on its buffers) and
+/// `AuctionWorker` (on the auction state). `None` means unlimited. When a cap is hit, a new bid
+/// only gets in by displacing a worse one. Commitments and encrypted bids cannot be ranked, so
+/// they are refused once a cap is hit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BidLimits {
+    pub max_bids_per_bidder: Option<usize>,
+    pub max_bids_per_auction: Option<usize>,
 }
 
 /// Represents a Service Level Agreement (AuctionInfo) provided by the seller, which is the basis for an auction.
@@ -35,6 +425,8 @@ pub struct AuctionInfo {
     pub start_time: u64,
     pub end_time: u64,
     pub seller_signature: String,
+    /// The minimum amount any winning bid must pay. `None` means the auction has no reserve.
+    pub reserve_price: Option<u64>,
 }
 
 impl AuctionInfo {
@@ -48,16 +440,8 @@ impl AuctionInfo {
         end_time: u64,
         seller_signature: String,
     ) -> Self {
-        AuctionInfo {
-            id: compute_hash(&[
-                chain_id.to_be_bytes().as_ref(),
-                block_number.to_be_bytes().as_ref(),
-                seller_address.as_bytes(),
-                blockspace_size.to_be_bytes().as_ref(),
-                start_time.to_be_bytes().as_ref(),
-                end_time.to_be_bytes().as_ref(),
-                seller_signature.as_bytes(),
-            ]),
+        let mut auction_info = AuctionInfo {
+            id: AuctionId::new(),
             chain_id,
             block_number,
             seller_address,
@@ -65,7 +449,55 @@ impl AuctionInfo {
             start_time,
             end_time,
             seller_signature,
+            reserve_price: None,
+        };
+        auction_info.id = auction_info.compute_id();
+        auction_info
+    }
+
+    /// Sets the seller's signature over `signing_hash()` and recomputes the auction ID accordingly.
+    pub fn with_seller_signature(mut self, seller_signature: String) -> Self {
+        self.seller_signature = seller_signature;
+        self.id = self.compute_id();
+        self
+    }
+
+    /// Sets the reserve price of the auction and recomputes its ID accordingly.
+    pub fn with_reserve_price(mut self, reserve_price: u64) -> Self {
+        self.reserve_price = Some(reserve_price);
+        self.id = self.compute_id();
+        self
+    }
+
+    /// Computes the EIP-712 digest the seller signs (see `utils::eip712`). It covers the auction
+    /// parameters, but not the ID and the signature, which are derived from them.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        eip712::auction_info_digest(self)
+    }
+
+    /// Computes the auction ID from the auction parameters.
+    /// The reserve price is only hashed when set, so auctions without a reserve keep their IDs.
+    fn compute_id(&self) -> AuctionId {
+        let chain_id = self.chain_id.to_be_bytes();
+        let block_number = self.block_number.to_be_bytes();
+        let blockspace_size = self.blockspace_size.to_be_bytes();
+        let start_time = self.start_time.to_be_bytes();
+        let end_time = self.end_time.to_be_bytes();
+        let reserve_price = self.reserve_price.map(u64::to_be_bytes);
+
+        let mut inputs: Vec<&[u8]> = vec![
+            &chain_id,
+            &block_number,
+            self.seller_address.as_bytes(),
+            &blockspace_size,
+            &start_time,
+            &end_time,
+            self.seller_signature.as_bytes(),
+        ];
+        if let Some(ref reserve_price) = reserve_price {
+            inputs.push(reserve_price);
         }
+        compute_hash(&inputs)
     }
 }
 
@@ -89,34 +521,486 @@ impl PartialEq for AuctionInfo {
 
 impl Eq for AuctionInfo {}
 
-/// Represents the state of an auction, including the AuctionInfo, current highest bid, winner, all bids, and whether it is ended.
+/// Represents a bid that was allocated part of the auction's blockspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuctionWinner {
+    pub bidder_addr: String,
+    /// The hash of the winning bid (see `Bid::hash`).
+    pub bid_hash: String,
+    /// The effective value of the winning bid (see `Bid::effective_value`).
+    pub bid_amount: u64,
+    pub allocated_size: u64,
+    /// The amount charged to this winner under the chain's `PricingRule`.
+    pub price: u64,
+}
+
+/// Represents the state of an auction, including the AuctionInfo, current highest bid, clearing price, winners, all bids, and whether it is ended.
 #[derive(Debug, Clone)]
 pub struct AuctionState {
     pub auction_info: AuctionInfo,
     pub highest_bid: u64,
-    pub winner: Option<String>,
+    /// The total amount actually charged to the winners under the chain's `PricingRule`.
+    pub clearing_price: u64,
+    /// Winning bids ordered by bid amount, whose allocated sizes fit within `blockspace_size`.
+    pub winners: Vec<AuctionWinner>,
+    /// Whether a bid reached the auction's reserve price. Always `true` when there is no reserve.
+    pub reserve_met: bool,
+    /// The rule used to order bids with equal amounts.
+    pub tie_break_rule: TieBreakRule,
+    /// The effective end time, which is later than `auction_info.end_time` after soft-close extensions.
+    pub end_time: u64,
+    /// At most one bid per bidder, the one with the highest nonce.
     pub bids: Vec<Bid>,
+    /// The highest nonce seen per bidder, including nonces of withdrawn bids.
+    pub bidder_nonces: HashMap<String, u64>,
+    /// Whether `bids` changed since the winners were last determined.
+    pub winners_outdated: bool,
+    /// The leg of a winning `PackageBid`, which gets the top of the block before any single-block bid.
+    pub package_leg: Option<Bid>,
+    /// Bid commitments that have not been revealed yet (commit-reveal auctions only).
+    pub commitments: Vec<BidCommitment>,
+    /// Bid commitments that were never revealed before the reveal window closed.
+    pub forfeited_commitments: Vec<BidCommitment>,
+    /// Hex-encoded public key bidders encrypt their bids to (encrypted auctions only).
+    pub encryption_public_key: Option<String>,
+    /// Bids that stay encrypted until the auction ends (encrypted auctions only).
+    pub encrypted_bids: Vec<EncryptedBid>,
+    /// The hash of every submission accepted into the auction, in acceptance order: bids (see
+    /// `Bid::hash`), the package leg, commitments, and encrypted bids. It is append-only, and a
+    /// bid restored after its removal is logged again.
+    pub bid_log: Vec<String>,
+    /// The submissions that left the auction before it ended, in the order they left.
+    pub bid_removals: Vec<BidRemoval>,
+    /// The Merkle commitment over `bid_log`, built once when the auction ends.
+    pub bid_tree: Option<MerkleTree>,
+    /// The Merkle commitment over the hashes of `bid_removals`, built along with `bid_tree`.
+    pub removal_tree: Option<MerkleTree>,
+    pub phase: AuctionPhase,
+    /// Whether the auction is closed, i.e., `phase` is `Settling`, `Ended`, or `Cancelled`.
     pub is_ended: bool,
 }
 
+/// Why a submission left an auction before it ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemovalReason {
+    /// Evicted to keep the auction, or `BidService`'s buffer of its bids, within a bid cap.
+    Evicted,
+    /// Replaced by a bid of the same bidder with a higher nonce.
+    Replaced,
+    /// Withdrawn by the bidder with a `BidCancellation`.
+    Cancelled,
+    /// The leg of a cross-chain bid that lost another leg.
+    Withdrawn,
+    /// The leg of a cross-chain bid whose other legs were rejected on submission.
+    Reverted,
+    /// A commitment or encrypted bid that was opened into the bid it sealed.
+    Opened,
+    /// An encrypted bid that failed to decrypt, or whose bid was rejected. Also a bid that
+    /// `BidService` issued a receipt for, but that the auction rejected when it was flushed.
+    Rejected,
+    /// A commitment that was not revealed before the reveal window closed.
+    Forfeited,
+}
+
+impl RemovalReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RemovalReason::Evicted => "evicted",
+            RemovalReason::Replaced => "replaced",
+            RemovalReason::Cancelled => "cancelled",
+            RemovalReason::Withdrawn => "withdrawn",
+            RemovalReason::Reverted => "reverted",
+            RemovalReason::Opened => "opened",
+            RemovalReason::Rejected => "rejected",
+            RemovalReason::Forfeited => "forfeited",
+        }
+    }
+}
+
+/// Records that a submission in the bid log left the auction, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BidRemoval {
+    /// The hash the submission was logged with.
+    pub submission_hash: String,
+    pub reason: RemovalReason,
+}
+
+impl BidRemoval {
+    /// Computes the leaf hash of the removal in the removal tree.
+    pub fn hash(&self) -> String {
+        compute_framed_hash(&[
+            b"bid-removal",
+            self.submission_hash.as_bytes(),
+            self.reason.as_str().as_bytes(),
+        ])
+    }
+}
+
+/// The phase an auction is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionPhase {
+    /// Bids (or, in commit-reveal auctions, bid commitments) are being accepted.
+    Bidding,
+    /// Commit-reveal auctions only: bidders reveal the bids they committed to.
+    Reveal,
+    /// The auction is closed to bids, and its winners are held until the cross-chain bids with a
+    /// leg in it are settled together with the other auctions they span.
+    Settling,
+    /// The auction is closed and its outcome is final.
+    Ended,
+    /// The auction was cancelled by its seller and has no outcome.
+    Cancelled,
+}
+
 impl AuctionState {
     /// Creates a new `AuctionState` based on the provided AuctionInfo.
     pub fn new(auction_info: AuctionInfo) -> Self {
+        let reserve_met = auction_info.reserve_price.is_none();
+        let end_time = auction_info.end_time;
         AuctionState {
             auction_info,
             highest_bid: 0,
-            winner: None,
+            clearing_price: 0,
+            winners: Vec::new(),
+            reserve_met,
+            tie_break_rule: TieBreakRule::default(),
+            end_time,
             bids: Vec::new(),
+            bidder_nonces: HashMap::new(),
+            winners_outdated: false,
+            package_leg: None,
+            commitments: Vec::new(),
+            forfeited_commitments: Vec::new(),
+            encryption_public_key: None,
+            encrypted_bids: Vec::new(),
+            bid_log: Vec::new(),
+            bid_removals: Vec::new(),
+            bid_tree: None,
+            removal_tree: None,
+            phase: AuctionPhase::Bidding,
             is_ended: false,
         }
     }
+
+    /// Closes the auction to bids. Its outcome is final once it is ended.
+    pub fn close(&mut self) {
+        self.phase = AuctionPhase::Settling;
+        self.is_ended = true;
+    }
+
+    /// Marks the auction as ended.
+    pub fn end(&mut self) {
+        self.phase = AuctionPhase::Ended;
+        self.is_ended = true;
+    }
+
+    /// Returns the winning bids, in winner order.
+    pub fn winning_bids(&self) -> Vec<&Bid> {
+        self.winners
+            .iter()
+            .filter_map(|winner| {
+                self.package_leg
+                    .iter()
+                    .chain(self.bids.iter())
+                    .find(|b| b.hash() == winner.bid_hash)
+            })
+            .collect()
+    }
+
+    /// Appends an accepted submission, by its hash, to the bid log.
+    pub fn log_submission(&mut self, submission_hash: String) {
+        self.bid_log.push(submission_hash);
+    }
+
+    /// Logs a submission that was accepted but removed before it entered the auction, e.g., a
+    /// bid that `BidService` issued a receipt for and then evicted from its buffer, along with
+    /// its removal. Every receipted bid can then be proven either included or removed.
+    pub fn log_dropped_submission(&mut self, submission_hash: String, reason: RemovalReason) {
+        self.log_submission(submission_hash.clone());
+        self.record_removal(submission_hash, reason);
+    }
+
+    /// Records that a submission, by its hash, left the auction.
+    pub fn record_removal(&mut self, submission_hash: String, reason: RemovalReason) {
+        self.bid_removals.push(BidRemoval {
+            submission_hash,
+            reason,
+        });
+    }
+
+    /// Builds the Merkle commitments over the bid log and the removals, in the order they were
+    /// recorded. They are built once, so a commitment never changes after it is published.
+    pub fn commit_bids(&mut self) {
+        if self.bid_tree.is_some() {
+            return;
+        }
+        let removal_hashes: Vec<String> = self.bid_removals.iter().map(BidRemoval::hash).collect();
+        self.bid_tree = Some(MerkleTree::new(&self.bid_log));
+        self.removal_tree = Some(MerkleTree::new(&removal_hashes));
+    }
+
+    /// Marks the auction as cancelled, discarding any outcome determined so far.
+    pub fn cancel(&mut self) {
+        self.phase = AuctionPhase::Cancelled;
+        self.is_ended = true;
+        self.winners.clear();
+        self.clearing_price = 0;
+    }
 }
 
+/// A request by the seller to cancel an auction that is queued or running.
 #[derive(Debug, Clone)]
+pub struct AuctionCancellation {
+    pub chain_id: ChainId,
+    pub auction_id: AuctionId,
+    pub seller_address: String,
+    pub seller_signature: String,
+}
+
+impl AuctionCancellation {
+    /// Computes the message hash the seller signs.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&[
+            b"cancel-auction",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.seller_address.as_bytes(),
+        ])
+    }
+}
+
+/// Events broadcast by `AuctionManager` to interested parties (e.g., `BidService` and bidders).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuctionEvent {
+    /// The auction was cancelled by its seller; bids for it are discarded.
+    Cancelled {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+    },
+    /// The auction ended; bids for it are no longer accepted.
+    Ended {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+    },
+}
+
+/// The finalized outcome of an auction, signed by the Lightbulb operator so that it can be
+/// verified by sequencers and bidders wherever it is passed on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuctionResult {
     pub chain_id: ChainId,
     pub auction_id: AuctionId,
-    pub winner: String,
+    /// The winning bidders, in the order their transactions are placed.
+    pub winners: Vec<AuctionWinner>,
+    pub clearing_price: u64,
+    /// The hash of the winning bids' transactions, in the order of `winners`. It does not cover
+    /// the transactions that fill the rest of the blockspace (see `AuctionWorker::get_block_ordering`).
+    pub winning_tx_list_hash: String,
+    /// The number of bids that competed in the auction.
+    pub bid_count: usize,
+    /// The root of the Merkle tree over every submission accepted into the auction, including
+    /// those that left it (see `AuctionState::bid_log`).
+    pub bid_root: String,
+    /// The root of the Merkle tree over the submissions that left the auction, and why (see
+    /// `AuctionState::bid_removals`).
+    pub removal_root: String,
+    /// The effective end time of the auction, including soft-close extensions.
+    pub end_time: u64,
+    /// The address of the operator key that signed the result.
+    pub operator_address: String,
+    /// The operator's signature over `hash()`.
+    pub operator_signature: String,
+}
+
+impl AuctionResult {
+    /// Creates the result of an ended auction and signs it with the operator key. The manager
+    /// does so once per auction, after the auction has been settled.
+    pub fn new(
+        auction_state: &AuctionState,
+        operator_key: &Secp256k1Key,
+    ) -> Result<Self, SignatureError> {
+        let winning_txs: Vec<&Tx> = auction_state
+            .winning_bids()
+            .into_iter()
+            .flat_map(|b| b.tx_list.iter())
+            .collect();
+        let gas_limits: Vec<[u8; 8]> = winning_txs
+            .iter()
+            .map(|tx| tx.gas_limit.to_be_bytes())
+            .collect();
+        let mut inputs: Vec<&[u8]> = Vec::new();
+        for (tx, gas_limit) in winning_txs.iter().zip(gas_limits.iter()) {
+            inputs.push(tx.tx_data.as_bytes());
+            inputs.push(gas_limit);
+        }
+
+        let mut result = AuctionResult {
+            chain_id: auction_state.auction_info.chain_id,
+            auction_id: auction_state.auction_info.id.clone(),
+            winners: auction_state.winners.clone(),
+            clearing_price: auction_state.clearing_price,
+            winning_tx_list_hash: compute_hash(&inputs),
+            bid_count: auction_state.bids.len() + auction_state.package_leg.iter().count(),
+            bid_root: auction_state
+                .bid_tree
+                .as_ref()
+                .map(MerkleTree::root)
+                .unwrap_or_else(|| MerkleTree::new::<&str>(&[]).root()),
+            removal_root: auction_state
+                .removal_tree
+                .as_ref()
+                .map(MerkleTree::root)
+                .unwrap_or_else(|| MerkleTree::new::<&str>(&[]).root()),
+            end_time: auction_state.end_time,
+            operator_address: operator_key.address(),
+            operator_signature: String::new(),
+        };
+        result.operator_signature = sign_operator_message(&result.hash(), operator_key)?;
+        Ok(result)
+    }
+
+    /// Computes the hash of the result, covering every field except the signature.
+    pub fn hash(&self) -> String {
+        let chain_id = self.chain_id.to_be_bytes();
+        let clearing_price = self.clearing_price.to_be_bytes();
+        let bid_count = (self.bid_count as u64).to_be_bytes();
+        let end_time = self.end_time.to_be_bytes();
+        let winner_amounts: Vec<[u8; 24]> = self
+            .winners
+            .iter()
+            .map(|winner| {
+                let mut amounts = [0u8; 24];
+                amounts[..8].copy_from_slice(&winner.bid_amount.to_be_bytes());
+                amounts[8..16].copy_from_slice(&winner.allocated_size.to_be_bytes());
+                amounts[16..].copy_from_slice(&winner.price.to_be_bytes());
+                amounts
+            })
+            .collect();
+
+        // Prefixed, so that a signed result can never pass for another operator message
+        let mut inputs: Vec<&[u8]> = vec![b"auction-result", &chain_id, self.auction_id.as_bytes()];
+        for (winner, amounts) in self.winners.iter().zip(winner_amounts.iter()) {
+            inputs.push(winner.bidder_addr.as_bytes());
+            inputs.push(winner.bid_hash.as_bytes());
+            inputs.push(amounts);
+        }
+        inputs.extend_from_slice(&[
+            &clearing_price,
+            self.winning_tx_list_hash.as_bytes(),
+            &bid_count,
+            self.bid_root.as_bytes(),
+            self.removal_root.as_bytes(),
+            &end_time,
+            self.operator_address.as_bytes(),
+        ]);
+        compute_framed_hash(&inputs)
+    }
+
+    /// Checks that the result was signed by the given operator address and has not been altered.
+    pub fn verify(&self, operator_address: &str) -> bool {
+        verify_operator_message(
+            &self.hash(),
+            &self.operator_address,
+            &self.operator_signature,
+            operator_address,
+        )
+    }
+
+    /// Checks an inclusion proof of a bid, by its hash, against the result's `bid_root`.
+    pub fn verify_bid_inclusion(&self, bid_hash: &str, proof: &MerkleProof) -> bool {
+        merkle::verify_proof(bid_hash.as_bytes(), proof, &self.bid_root)
+    }
+
+    /// Checks a proof that a submission left the auction against the result's `removal_root`.
+    pub fn verify_bid_removal(&self, removal: &BidRemoval, proof: &MerkleProof) -> bool {
+        merkle::verify_proof(removal.hash().as_bytes(), proof, &self.removal_root)
+    }
+}
+
+/// A receipt for a bid accepted by `BidService`, signed by the Lightbulb operator. It proves
+/// that the bid arrived at `arrival_time`, so a bidder can dispute an outcome that left it out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidReceipt {
+    pub chain_id: ChainId,
+    pub auction_id: AuctionId,
+    pub bidder_addr: String,
+    /// The hash of the received bid (see `Bid::hash`).
+    pub bid_hash: String,
+    /// The server time at which the bid arrived (Unix ms).
+    pub arrival_time: u64,
+    /// The arrival order of the bid within its auction.
+    pub sequence: u64,
+    /// The address of the operator key that signed the receipt.
+    pub operator_address: String,
+    /// The operator's signature over `hash()`.
+    pub operator_signature: String,
+}
+
+impl BidReceipt {
+    /// Creates the receipt of a bid stamped with its arrival, and signs it with the operator key.
+    pub fn new(bid: &Bid, operator_key: &Secp256k1Key) -> Result<Self, SignatureError> {
+        let mut receipt = BidReceipt {
+            chain_id: bid.chain_id,
+            auction_id: bid.auction_id.clone(),
+            bidder_addr: bid.bidder_addr.clone(),
+            bid_hash: bid.hash(),
+            arrival_time: bid.arrival_time,
+            sequence: bid.sequence,
+            operator_address: operator_key.address(),
+            operator_signature: String::new(),
+        };
+        receipt.operator_signature = sign_operator_message(&receipt.hash(), operator_key)?;
+        Ok(receipt)
+    }
+
+    /// Computes the hash of the receipt, covering every field except the signature.
+    pub fn hash(&self) -> String {
+        // Prefixed, so that a signed receipt can never pass for another operator message
+        compute_framed_hash(&[
+            b"bid-receipt",
+            &self.chain_id.to_be_bytes(),
+            self.auction_id.as_bytes(),
+            self.bidder_addr.as_bytes(),
+            self.bid_hash.as_bytes(),
+            &self.arrival_time.to_be_bytes(),
+            &self.sequence.to_be_bytes(),
+            self.operator_address.as_bytes(),
+        ])
+    }
+
+    /// Checks that the receipt was signed by the given operator address and has not been altered.
+    pub fn verify(&self, operator_address: &str) -> bool {
+        verify_operator_message(
+            &self.hash(),
+            &self.operator_address,
+            &self.operator_signature,
+            operator_address,
+        )
+    }
+}
+
+/// Signs the hex-encoded hash of a message emitted by the operator.
+fn sign_operator_message(
+    hash: &str,
+    operator_key: &Secp256k1Key,
+) -> Result<String, SignatureError> {
+    operator_key.sign(&signing::decode_digest(hash)?)
+}
+
+/// Checks that a message naming `signer_address` as its signer was signed by that address, and
+/// that it is the expected `operator_address`.
+fn verify_operator_message(
+    hash: &str,
+    signer_address: &str,
+    signature: &str,
+    operator_address: &str,
+) -> bool {
+    signing::decode_digest(hash)
+        .and_then(|digest| signing::recover_address(&digest, signature))
+        .is_ok_and(|signer| {
+            signer.eq_ignore_ascii_case(operator_address)
+                && signer.eq_ignore_ascii_case(signer_address)
+        })
 }
 
 #[derive(Debug)]
@@ -128,7 +1012,12 @@ pub struct WorkerMessage {
 
 #[derive(Debug)]
 pub enum WorkerMessageType {
-    AuctionEnded,
+    /// The auction closed to bids and waits to be settled by the manager.
+    AuctionClosed,
+    /// The bidding phase of a commit-reveal auction closed and its reveal window opened.
+    RevealStarted,
+    /// The auction's end time was extended by the soft-close rule to the given Unix timestamp (ms).
+    AuctionExtended(u64),
     AuctionProcessing,
     Idle,
 }
@@ -24,8 +24,8 @@ impl AuctionRepository for SqliteAuctionRepository {
     /// Inserts a new auction into the database.
     async fn create_auction(&self, auction_info: AuctionInfo) -> Result<(), DatabaseError> {
         let query = r#"
-            INSERT INTO auctions (id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO auctions (id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature, reserve_price)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         sqlx::query(query)
@@ -37,6 +37,11 @@ impl AuctionRepository for SqliteAuctionRepository {
             .bind(auction_info.start_time as i64)
             .bind(auction_info.end_time as i64)
             .bind(&auction_info.seller_signature)
+            .bind(
+                auction_info
+                    .reserve_price
+                    .map(|reserve_price| reserve_price as i64),
+            )
             .execute(&self.db_pool.pool)
             .await?;
 
@@ -49,7 +54,7 @@ impl AuctionRepository for SqliteAuctionRepository {
         auction_id: &str,
     ) -> Result<Option<AuctionInfo>, DatabaseError> {
         let query = r#"
-            SELECT id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature
+            SELECT id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature, reserve_price
             FROM auctions
             WHERE id = ?
         "#;
@@ -65,7 +70,7 @@ impl AuctionRepository for SqliteAuctionRepository {
     /// Lists all auctions stored in the database.
     async fn list_auctions(&self) -> Result<Vec<AuctionInfo>, DatabaseError> {
         let query = r#"
-            SELECT id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature
+            SELECT id, chain_id, block_number, seller_address, blockspace_size, start_time, end_time, seller_signature, reserve_price
             FROM auctions
         "#;
 
@@ -112,6 +117,7 @@ mod tests {
             start_time: 1633036800,
             end_time: 1633123200,
             seller_signature: "test_signature".to_string(),
+            reserve_price: Some(1000),
         };
 
         // Test create_auction
@@ -134,6 +140,7 @@ mod tests {
         assert_eq!(fetched.start_time as i64, auction_info.start_time as i64);
         assert_eq!(fetched.end_time as i64, auction_info.end_time as i64);
         assert_eq!(fetched.seller_signature, auction_info.seller_signature);
+        assert_eq!(fetched.reserve_price, auction_info.reserve_price);
 
         Ok(())
     }
@@ -154,6 +161,7 @@ mod tests {
             start_time: 1633036801,
             end_time: 1633123201,
             seller_signature: "signature1".to_string(),
+            reserve_price: None,
         };
 
         let auction2 = AuctionInfo {
@@ -165,6 +173,7 @@ mod tests {
             start_time: 1633036802,
             end_time: 1633123202,
             seller_signature: "signature2".to_string(),
+            reserve_price: None,
         };
 
         repo.create_auction(auction1.clone()).await?;
@@ -195,6 +204,7 @@ mod tests {
             start_time: 1633036803,
             end_time: 1633123203,
             seller_signature: "signature3".to_string(),
+            reserve_price: None,
         };
 
         repo.create_auction(auction.clone()).await?;
@@ -229,6 +239,7 @@ mod tests {
             start_time: 1633036804,
             end_time: 1633123204,
             seller_signature: "signature4".to_string(),
+            reserve_price: None,
         };
 
         // First insertion
@@ -5,8 +5,12 @@ use lightbulb::{
         auction::AuctionManager,
         domain::{AuctionInfo, Bid, ChainId, ChainInfo, Tx},
     },
-    services::{bid::BidService, registry::RegistryService},
-    utils::helpers::current_unix_ms,
+    services::{
+        bid::BidService,
+        operator::{OperatorService, DEFAULT_ROTATION_OVERLAP_MS},
+        registry::RegistryService,
+    },
+    utils::{helpers::current_unix_ms, signing::Secp256k1Key},
 };
 use tokio::time::{sleep, Duration};
 
@@ -25,17 +29,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Define the ChainId to use for the test
     let test_chain_id: ChainId = 1;
 
+    // Sellers sign their auctions and bidders sign their bids
+    let seller_key = Secp256k1Key::generate();
+    let bidder_keys: Vec<Secp256k1Key> = (0..3).map(|_| Secp256k1Key::generate()).collect();
+
     registry_service
         .register_chain(
             test_chain_id,
             ChainInfo {
                 gas_limit: 1000,
-                registered_sellers: vec!["0xTestSeller".to_string()],
+                registered_sellers: vec![seller_key.address()],
+                ..Default::default()
             },
         )
         .await?;
 
-    println!("ChainId 1 registered with seller 0xTestSeller");
+    println!("ChainId 1 registered with seller {}", seller_key.address());
 
     let chain_ids = registry_service.get_chain_ids().await;
     assert_eq!(chain_ids.len(), 1, "ChainId registration failed");
@@ -45,8 +54,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     println!("ChainIds registered: {:?}", chain_ids);
 
-    // 2. Setup AuctionManager and BidService
-    let auction_manager = Arc::new(AuctionManager::new(&registry_service).await);
+    // 2. Setup AuctionManager and BidService. The operator key is loaded from the keystore named
+    // by LIGHTBULB_KEYSTORE, if any; otherwise this run signs with a key of its own
+    let operator = match std::env::var("LIGHTBULB_KEYSTORE") {
+        Ok(path) => {
+            let password = std::env::var("LIGHTBULB_KEYSTORE_PASSWORD").unwrap_or_default();
+            OperatorService::from_keystore(path, &password, DEFAULT_ROTATION_OVERLAP_MS)?
+        }
+        Err(_) => OperatorService::new(Secp256k1Key::generate(), DEFAULT_ROTATION_OVERLAP_MS),
+    };
+    let auction_manager = Arc::new(AuctionManager::new(&registry_service, operator).await);
     println!("AuctionManager created");
 
     let mut flush_intervals: HashMap<ChainId, Duration> = HashMap::new();
@@ -63,12 +80,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let auction_info = AuctionInfo::new(
         test_chain_id,
         100, // block_number
-        "0xTestSeller".to_string(),
+        seller_key.address(),
         500,        // blockspace_size
         now - 1000, // start_time: 1 second in the past
         now + 5000, // end_time: 5 seconds in the future
-        "0xSellerSignature".to_string(),
+        String::new(),
     );
+    let seller_signature = seller_key.sign(&auction_info.signing_hash())?;
+    let auction_info = auction_info.with_seller_signature(seller_signature);
 
     println!("AuctionInfo created: {:?}", auction_info);
 
@@ -95,39 +114,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // 6. Submit bids via BidService
-    let bid1 = Bid {
+    let mut bid1 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder1".to_string(),
+        bidder_addr: bidder_keys[0].address(),
         bid_amount: 1000,
-        bidder_signature: "0xBidder1Signature".to_string(),
+        blockspace_size: 500, // consumes the whole blockspace
         tx_list: vec![Tx {
             tx_data: "tx1".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
-    let bid2 = Bid {
+    let mut bid2 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder2".to_string(),
+        bidder_addr: bidder_keys[1].address(),
         bid_amount: 1500, // Highest bid
-        bidder_signature: "0xBidder2Signature".to_string(),
+        blockspace_size: 500,
         tx_list: vec![Tx {
             tx_data: "tx2".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
-    let bid3 = Bid {
+    let mut bid3 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder3".to_string(),
+        bidder_addr: bidder_keys[2].address(),
         bid_amount: 1200,
-        bidder_signature: "0xBidder3Signature".to_string(),
+        blockspace_size: 500,
         tx_list: vec![Tx {
             tx_data: "tx3".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
+    for (bid, bidder_key) in [&mut bid1, &mut bid2, &mut bid3]
+        .into_iter()
+        .zip(bidder_keys.iter())
+    {
+        bid.bidder_signature = bidder_key.sign(&bid.signing_hash())?;
+    }
+
     println!("Bids created");
 
     bid_service.store_bid(bid1.clone()).await?;
@@ -162,14 +194,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(state) => {
             assert!(state.is_ended, "Auction state is not marked as ended");
             assert_eq!(
-                state.winner,
-                Some("0xBidder2".to_string()),
+                state.winners.len(),
+                1,
+                "Only one bid should fit into the blockspace"
+            );
+            assert_eq!(
+                state.winners[0].bidder_addr,
+                bidder_keys[1].address(),
                 "Winner does not match the highest bid"
             );
             assert_eq!(
                 state.highest_bid, 1500,
                 "Highest bid amount does not match the expected value"
             );
+            assert_eq!(
+                state.clearing_price, 1500,
+                "Clearing price does not match the first-price rule"
+            );
             assert_eq!(
                 state.bids.len(),
                 3,
@@ -177,8 +218,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
             println!(
                 "Auction state verified: Winner: {}, Highest Bid: {}",
-                state.winner.unwrap(),
-                state.highest_bid
+                state.winners[0].bidder_addr, state.highest_bid
             );
         }
         Err(e) => panic!("Failed to retrieve auction state: {}", e),
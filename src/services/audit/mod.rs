@@ -0,0 +1,883 @@
+use std::{
+    ffi::OsString,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex as StdMutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{
+    core::domain::{AuctionId, ChainId},
+    services::operator::OperatorService,
+    utils::{
+        errors::{AuditError, SignatureError},
+        helpers::{compute_framed_hash, current_unix_ms},
+        signing,
+    },
+};
+
+/// The `prev_hash` of the first entry of an audit log.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// How many entries are appended between two signed heads by default.
+pub const DEFAULT_HEAD_INTERVAL: u64 = 64;
+
+/// A lifecycle event recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AuditEvent {
+    ChainRegistered {
+        chain_id: ChainId,
+    },
+    AuctionQueued {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        seller_address: String,
+    },
+    AuctionStarted {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+    },
+    /// A bid was accepted by `BidService`, which issued a `BidReceipt` for it.
+    BidAccepted {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bidder_addr: String,
+        bid_hash: String,
+        sequence: u64,
+    },
+    /// A bidder withdrew their bids up to `nonce` with a `BidCancellation`.
+    BidCancelled {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bidder_addr: String,
+        nonce: u64,
+    },
+    /// A bid commitment was accepted by a commit-reveal auction.
+    CommitmentAccepted {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bidder_addr: String,
+        commitment_hash: String,
+    },
+    /// A committed bid was revealed and accepted.
+    BidRevealed {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bidder_addr: String,
+        bid_hash: String,
+    },
+    /// An encrypted bid was accepted by an encrypted auction.
+    EncryptedBidAccepted {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        bidder_addr: String,
+        encrypted_bid_hash: String,
+    },
+    /// A package bid was accepted, with the hashes of its legs in block order.
+    PackageBidAccepted {
+        chain_id: ChainId,
+        bidder_addr: String,
+        leg_hashes: Vec<String>,
+    },
+    /// A cross-chain bid was accepted, with the chain and hash of each of its legs.
+    CrossChainBidAccepted {
+        bidder_addr: String,
+        chain_ids: Vec<ChainId>,
+        leg_hashes: Vec<String>,
+    },
+    AuctionExtended {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        end_time: u64,
+    },
+    RevealStarted {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+    },
+    /// The auction ended. `result_hash` is the hash of its signed `AuctionResult`, if one was
+    /// produced. A result is signed once per auction, so it is never reissued.
+    AuctionEnded {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+        result_hash: Option<String>,
+    },
+    AuctionCancelled {
+        chain_id: ChainId,
+        auction_id: AuctionId,
+    },
+}
+
+/// An entry of the audit log, chained to the previous entry by its hash and signed by the
+/// operator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// The position of the entry in the log, starting at 0.
+    pub sequence: u64,
+    /// The time the entry was recorded (Unix ms).
+    pub timestamp: u64,
+    pub event: AuditEvent,
+    /// The `hash` of the previous entry, or `GENESIS_HASH` for the first one.
+    pub prev_hash: String,
+    /// The hash over all the other fields except the signature (see `AuditEntry::compute_hash`).
+    pub hash: String,
+    /// The address of the operator key that signed the entry.
+    pub operator_address: String,
+    /// The operator's signature over `hash`.
+    pub operator_signature: String,
+}
+
+impl AuditEntry {
+    /// Creates an entry following the entry with the hash `prev_hash`, and signs it with the
+    /// active operator key.
+    pub async fn new(
+        sequence: u64,
+        timestamp: u64,
+        event: AuditEvent,
+        prev_hash: String,
+        operator: &OperatorService,
+    ) -> Result<Self, AuditError> {
+        operator
+            .with_active_key(|key| {
+                let mut entry = AuditEntry {
+                    sequence,
+                    timestamp,
+                    event,
+                    prev_hash,
+                    hash: String::new(),
+                    operator_address: key.address(),
+                    operator_signature: String::new(),
+                };
+                entry.hash = entry.compute_hash();
+                let digest = signing::decode_digest(&entry.hash)?;
+                entry.operator_signature = key.sign(&digest)?;
+                Ok(entry)
+            })
+            .await
+            .map_err(|e: SignatureError| AuditError::Signing(e.to_string()))
+    }
+
+    /// Computes the hash of the entry, covering every field except `hash` and the signature.
+    pub fn compute_hash(&self) -> String {
+        let event = serde_json::to_vec(&self.event).unwrap_or_default();
+        // Prefixed, so that a signed entry can never pass for another operator message
+        compute_framed_hash(&[
+            b"audit-entry",
+            &self.sequence.to_be_bytes(),
+            &self.timestamp.to_be_bytes(),
+            &event,
+            self.prev_hash.as_bytes(),
+            self.operator_address.as_bytes(),
+        ])
+    }
+
+    /// Checks that the entry was signed by the operator key it names.
+    pub fn verify_signature(&self) -> bool {
+        verify_signed_hash(&self.hash, &self.operator_address, &self.operator_signature)
+    }
+}
+
+/// The number of entries in an audit log and the hash of the last one.
+///
+/// Every entry depends on all the entries before it, so a head kept outside the log pins its
+/// whole history. Passed to `verify_log`, it also reveals entries cut off the end of the log,
+/// which the chain alone cannot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditHead {
+    pub entry_count: u64,
+    pub last_hash: String,
+}
+
+impl AuditHead {
+    /// The head of an empty log.
+    pub fn genesis() -> Self {
+        AuditHead {
+            entry_count: 0,
+            last_hash: GENESIS_HASH.to_string(),
+        }
+    }
+}
+
+/// An `AuditHead` signed by the operator. `AuditLog` persists one every so many entries next to
+/// the log (see `heads_path`), so that entries cut off the end are detected when it is opened,
+/// and the heads can be published for anyone verifying the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedAuditHead {
+    pub head: AuditHead,
+    /// The time the head was signed (Unix ms).
+    pub timestamp: u64,
+    /// The address of the operator key that signed the head.
+    pub operator_address: String,
+    /// The operator's signature over `hash()`.
+    pub operator_signature: String,
+}
+
+impl SignedAuditHead {
+    /// Signs a head with the active operator key.
+    pub async fn new(head: AuditHead, operator: &OperatorService) -> Result<Self, AuditError> {
+        let mut signed_head = SignedAuditHead {
+            head,
+            timestamp: current_unix_ms(),
+            operator_address: String::new(),
+            operator_signature: String::new(),
+        };
+        (signed_head.operator_address, signed_head.operator_signature) =
+            sign_hash(&signed_head.hash(), operator).await?;
+        Ok(signed_head)
+    }
+
+    /// Computes the hash of the head that the operator signs.
+    pub fn hash(&self) -> String {
+        compute_framed_hash(&[
+            b"audit-head",
+            &self.head.entry_count.to_be_bytes(),
+            self.head.last_hash.as_bytes(),
+            &self.timestamp.to_be_bytes(),
+        ])
+    }
+
+    /// Checks that the head was signed by the operator key it names.
+    pub fn verify_signature(&self) -> bool {
+        verify_signed_hash(
+            &self.hash(),
+            &self.operator_address,
+            &self.operator_signature,
+        )
+    }
+}
+
+/// `AuditLog` is an append-only, tamper-evident log of auction lifecycle events, written to a
+/// local file with one JSON `AuditEntry` per line. Every entry is signed by the operator, and so
+/// are the heads persisted periodically next to the log.
+#[derive(Clone)]
+pub struct AuditLog {
+    /// Assigns each entry its sequence number and previous hash
+    state: Arc<Mutex<AuditState>>,
+
+    /// The log files, written on the blocking thread pool in the order the entries were assigned
+    files: Arc<(StdMutex<AuditFiles>, Condvar)>,
+
+    /// Signs the entries and heads
+    operator: OperatorService,
+
+    /// How many entries are appended between two signed heads
+    head_interval: u64,
+}
+
+struct AuditState {
+    /// The head including every entry assigned so far, written or not
+    head: AuditHead,
+    /// The head of the entries known to be on disk
+    written: AuditHead,
+    /// Bumped whenever `head` is rolled back after a failed write
+    epoch: u64,
+    /// The turn of the next write to the files
+    next_turn: u64,
+}
+
+struct AuditFiles {
+    file: File,
+    /// The length of the file up to the end of its last complete entry
+    len: u64,
+    /// The head of the entries on disk
+    written: AuditHead,
+    heads_file: File,
+    /// The turn of the write that may go next
+    turn: u64,
+}
+
+impl AuditLog {
+    /// Opens the audit log at `path`, creating it if it does not exist, with entries and heads
+    /// signed by `operator`.
+    ///
+    /// An existing log is verified first and appended to from its last entry. A partial last
+    /// line, left by a crash in the middle of a write, is truncated. The log must still contain
+    /// the last signed head persisted for it, so that entries cut off the end are detected.
+    pub fn open(path: impl AsRef<Path>, operator: OperatorService) -> Result<Self, AuditError> {
+        let path = path.as_ref();
+        let len = truncate_partial_line(path)?;
+        let heads_path = heads_path(path);
+        truncate_partial_line(&heads_path)?;
+
+        let last_head = read_heads(&heads_path)?.pop();
+        if let Some(signed_head) = &last_head {
+            if !signed_head.verify_signature() {
+                return Err(AuditError::InvalidHeadSignature(
+                    signed_head.head.entry_count,
+                ));
+            }
+        }
+        let head = verify_log(
+            path,
+            last_head.as_ref().map(|signed_head| &signed_head.head),
+            None,
+        )?;
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let heads_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&heads_path)?;
+
+        Ok(AuditLog {
+            state: Arc::new(Mutex::new(AuditState {
+                head: head.clone(),
+                written: head.clone(),
+                epoch: 0,
+                next_turn: 0,
+            })),
+            files: Arc::new((
+                StdMutex::new(AuditFiles {
+                    file,
+                    len,
+                    written: head,
+                    heads_file,
+                    turn: 0,
+                }),
+                Condvar::new(),
+            )),
+            operator,
+            head_interval: DEFAULT_HEAD_INTERVAL,
+        })
+    }
+
+    /// Sets how many entries are appended between two signed heads.
+    pub fn with_head_interval(mut self, head_interval: u64) -> Self {
+        self.head_interval = head_interval.max(1);
+        self
+    }
+
+    /// Appends an event to the log and returns its entry. The entry is on disk when this
+    /// returns; if writing it fails, the log is left as it was.
+    ///
+    /// Only the assignment of the entry's place in the chain is serialized here. The entry is
+    /// written on the blocking thread pool, in the order of assignment, so appends do not wait
+    /// for each other's `fsync` while holding the log. An entry assigned after one that fails to
+    /// be written fails as well, and the chain continues from the last entry on disk.
+    pub async fn append(&self, event: AuditEvent) -> Result<AuditEntry, AuditError> {
+        let (entry, epoch, write) = {
+            let mut state = self.state.lock().await;
+            let entry = AuditEntry::new(
+                state.head.entry_count,
+                current_unix_ms(),
+                event,
+                state.head.last_hash.clone(),
+                &self.operator,
+            )
+            .await?;
+            let head = AuditHead {
+                entry_count: entry.sequence + 1,
+                last_hash: entry.hash.clone(),
+            };
+
+            // Persisted along with the entry, once it is on disk
+            let signed_head = if head.entry_count.is_multiple_of(self.head_interval) {
+                match SignedAuditHead::new(head.clone(), &self.operator).await {
+                    Ok(signed_head) => Some(signed_head),
+                    Err(e) => {
+                        eprintln!("[Audit] Failed to sign head: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            let turn = state.next_turn;
+            state.next_turn += 1;
+            state.head = head;
+
+            let files = Arc::clone(&self.files);
+            let pending = entry.clone();
+            let write = tokio::task::spawn_blocking(move || {
+                write_in_turn(&files, turn, |files| {
+                    write_entry(files, &pending, signed_head.as_ref())
+                })
+            });
+            (entry, state.epoch, write)
+        };
+
+        let (result, written) = write.await.map_err(|e| AuditError::Io(e.to_string()))?;
+        let mut state = self.state.lock().await;
+        if written.entry_count > state.written.entry_count {
+            state.written = written.clone();
+        }
+        if result.is_err() && state.epoch == epoch {
+            // Entries assigned after this one chain to it and fail too, so the chain is
+            // continued from the last entry on disk
+            state.head = written;
+            state.epoch += 1;
+        }
+        result.map(|()| entry)
+    }
+
+    /// Appends an event to the log, reporting a failure instead of returning it. Used where an
+    /// event is recorded as a side effect of an operation that has already succeeded.
+    pub async fn record(&self, event: AuditEvent) {
+        if let Err(e) = self.append(event).await {
+            eprintln!("[Audit] Failed to record event: {}", e);
+        }
+    }
+
+    /// Returns the current head of the log, covering the entries on disk.
+    pub async fn head(&self) -> AuditHead {
+        self.state.lock().await.written.clone()
+    }
+
+    /// Signs the current head of the log and persists it next to the log, e.g., before
+    /// publishing it or shutting down. The head covers every entry appended so far, and is
+    /// persisted once they are on disk.
+    pub async fn sign_head(&self) -> Result<SignedAuditHead, AuditError> {
+        let write = {
+            let mut state = self.state.lock().await;
+            let signed_head = SignedAuditHead::new(state.head.clone(), &self.operator).await?;
+            let turn = state.next_turn;
+            state.next_turn += 1;
+
+            let files = Arc::clone(&self.files);
+            tokio::task::spawn_blocking(move || {
+                write_in_turn(&files, turn, |files| {
+                    if files.written != signed_head.head {
+                        return (
+                            Err(AuditError::EarlierWriteFailed(signed_head.head.entry_count)),
+                            files.written.clone(),
+                        );
+                    }
+                    let persisted =
+                        append_line(&mut files.heads_file, &signed_head).map(|()| signed_head);
+                    (persisted, files.written.clone())
+                })
+            })
+        };
+        let (result, _) = write.await.map_err(|e| AuditError::Io(e.to_string()))?;
+        result
+    }
+}
+
+/// Waits for the given turn to write to the log files, then runs `write`, which returns its
+/// result along with the head of the entries on disk afterwards. Runs on the blocking thread
+/// pool. Every turn handed out must be taken, or the writes after it never run.
+fn write_in_turn<T>(
+    files: &(StdMutex<AuditFiles>, Condvar),
+    turn: u64,
+    write: impl FnOnce(&mut AuditFiles) -> (Result<T, AuditError>, AuditHead),
+) -> (Result<T, AuditError>, AuditHead) {
+    let (files_mutex, turn_changed) = files;
+    let guard = files_mutex.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut guard = turn_changed
+        .wait_while(guard, |files| files.turn != turn)
+        .unwrap_or_else(PoisonError::into_inner);
+    let written = write(&mut guard);
+    guard.turn += 1;
+    turn_changed.notify_all();
+    written
+}
+
+/// Writes an entry to the log, followed by `signed_head` to the heads file if it is given. The
+/// entry must follow the last entry on disk; a partial line left by a failed write is dropped.
+fn write_entry(
+    files: &mut AuditFiles,
+    entry: &AuditEntry,
+    signed_head: Option<&SignedAuditHead>,
+) -> (Result<(), AuditError>, AuditHead) {
+    if entry.sequence != files.written.entry_count || entry.prev_hash != files.written.last_hash {
+        return (
+            Err(AuditError::EarlierWriteFailed(entry.sequence)),
+            files.written.clone(),
+        );
+    }
+
+    let mut line = match serde_json::to_string(entry) {
+        Ok(line) => line,
+        Err(e) => return (Err(AuditError::Io(e.to_string())), files.written.clone()),
+    };
+    line.push('\n');
+    let written = files
+        .file
+        .write_all(line.as_bytes())
+        .and_then(|()| files.file.sync_data());
+    if let Err(e) = written {
+        // Drop whatever part of the line made it to the file
+        if let Err(e) = files.file.set_len(files.len) {
+            eprintln!("[Audit] Failed to truncate a partial entry: {}", e);
+        }
+        return (Err(e.into()), files.written.clone());
+    }
+    files.len += line.len() as u64;
+    files.written = AuditHead {
+        entry_count: entry.sequence + 1,
+        last_hash: entry.hash.clone(),
+    };
+
+    if let Some(signed_head) = signed_head {
+        if let Err(e) = append_line(&mut files.heads_file, signed_head) {
+            eprintln!("[Audit] Failed to persist signed head: {}", e);
+        }
+    }
+    (Ok(()), files.written.clone())
+}
+
+/// Appends a value to a file as a line of JSON and syncs the file.
+fn append_line(file: &mut File, value: &impl Serialize) -> Result<(), AuditError> {
+    let mut line = serde_json::to_string(value).map_err(|e| AuditError::Io(e.to_string()))?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.sync_data()?;
+    Ok(())
+}
+
+/// Returns the path of the file that the signed heads of the audit log at `path` are persisted
+/// to: the log's path with `.heads` appended.
+pub fn heads_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut heads_path = OsString::from(path.as_ref());
+    heads_path.push(".heads");
+    PathBuf::from(heads_path)
+}
+
+/// Reads the signed heads persisted at `path`, oldest first. A missing file has none.
+pub fn read_heads(path: impl AsRef<Path>) -> Result<Vec<SignedAuditHead>, AuditError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    std::fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| {
+            serde_json::from_str(line).map_err(|_| AuditError::MalformedEntry(line_index + 1))
+        })
+        .collect()
+}
+
+/// Verifies the audit log at `path` and returns its head.
+///
+/// Checks that the entries are numbered from 0 without gaps, that each entry's hash matches its
+/// contents, that each entry is chained to the previous one, and that each entry is signed by
+/// the operator key it names. Edited, reordered, or removed entries break the chain. If
+/// `expected_head` is given, e.g., a signed head published earlier, the log must also contain
+/// that head, which reveals entries cut off the end. If `operator_addresses` is given, every
+/// entry must be signed by one of them, so that nobody else can rebuild a consistent chain.
+pub fn verify_log(
+    path: impl AsRef<Path>,
+    expected_head: Option<&AuditHead>,
+    operator_addresses: Option<&[String]>,
+) -> Result<AuditHead, AuditError> {
+    let path = path.as_ref();
+    let contents = if path.exists() {
+        std::fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+
+    let mut head = AuditHead::genesis();
+    for (line_index, line) in contents.lines().enumerate() {
+        let entry: AuditEntry =
+            serde_json::from_str(line).map_err(|_| AuditError::MalformedEntry(line_index + 1))?;
+
+        if entry.sequence != head.entry_count
+            || entry.prev_hash != head.last_hash
+            || entry.hash != entry.compute_hash()
+        {
+            return Err(AuditError::BrokenChain(head.entry_count));
+        }
+
+        let trusted = operator_addresses.is_none_or(|addresses| {
+            addresses
+                .iter()
+                .any(|address| address.eq_ignore_ascii_case(&entry.operator_address))
+        });
+        if !trusted || !entry.verify_signature() {
+            return Err(AuditError::InvalidSignature(entry.sequence));
+        }
+
+        if let Some(expected_head) = expected_head {
+            if entry.sequence + 1 == expected_head.entry_count
+                && entry.hash != expected_head.last_hash
+            {
+                return Err(AuditError::BrokenChain(entry.sequence));
+            }
+        }
+
+        head = AuditHead {
+            entry_count: entry.sequence + 1,
+            last_hash: entry.hash,
+        };
+    }
+
+    if let Some(expected_head) = expected_head {
+        if head.entry_count < expected_head.entry_count {
+            return Err(AuditError::Truncated {
+                expected: expected_head.entry_count,
+                found: head.entry_count,
+            });
+        }
+    }
+
+    Ok(head)
+}
+
+/// Truncates the file at `path` after its last complete line, dropping what a write interrupted
+/// by a crash left behind, and returns its length. A missing file has length 0.
+fn truncate_partial_line(path: &Path) -> Result<u64, AuditError> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let contents = std::fs::read(path)?;
+    let len = contents
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |position| position + 1);
+    if len < contents.len() {
+        eprintln!(
+            "[Audit] Dropping a partial line of {} bytes at the end of {}",
+            contents.len() - len,
+            path.display()
+        );
+        let file = OpenOptions::new().write(true).open(path)?;
+        file.set_len(len as u64)?;
+        file.sync_data()?;
+    }
+    Ok(len as u64)
+}
+
+/// Signs a hex-encoded hash with the active operator key, returning the signer's address along
+/// with the signature.
+async fn sign_hash(hash: &str, operator: &OperatorService) -> Result<(String, String), AuditError> {
+    let digest = signing::decode_digest(hash).map_err(|e| AuditError::Signing(e.to_string()))?;
+    operator
+        .sign(&digest)
+        .await
+        .map_err(|e| AuditError::Signing(e.to_string()))
+}
+
+/// Checks that a hex-encoded hash was signed by `operator_address`.
+fn verify_signed_hash(hash: &str, operator_address: &str, operator_signature: &str) -> bool {
+    signing::decode_digest(hash)
+        .and_then(|digest| signing::recover_address(&digest, operator_signature))
+        .is_ok_and(|signer| signer.eq_ignore_ascii_case(operator_address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{services::operator::DEFAULT_ROTATION_OVERLAP_MS, utils::signing::Secp256k1Key};
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "lightbulb-audit-{}-{}-{}.log",
+            name,
+            std::process::id(),
+            current_unix_ms()
+        ))
+    }
+
+    fn remove_log(path: &Path) {
+        std::fs::remove_file(path).unwrap();
+        let _ = std::fs::remove_file(heads_path(path));
+    }
+
+    fn operator() -> OperatorService {
+        OperatorService::new(Secp256k1Key::generate(), DEFAULT_ROTATION_OVERLAP_MS)
+    }
+
+    async fn write_log(path: &Path, operator: OperatorService) -> AuditHead {
+        let audit_log = AuditLog::open(path, operator).unwrap();
+        audit_log
+            .append(AuditEvent::ChainRegistered { chain_id: 1 })
+            .await
+            .unwrap();
+        for auction_id in ["auction1", "auction2"] {
+            audit_log
+                .append(AuditEvent::AuctionStarted {
+                    chain_id: 1,
+                    auction_id: auction_id.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        audit_log.sign_head().await.unwrap();
+        audit_log.head().await
+    }
+
+    #[tokio::test]
+    async fn test_log_is_chained_and_resumed() {
+        let path = temp_log_path("resume");
+        let operator = operator();
+        let head = write_log(&path, operator.clone()).await;
+        assert_eq!(head.entry_count, 3);
+        let operator_addresses = [operator.address().await];
+        assert_eq!(
+            verify_log(&path, Some(&head), Some(&operator_addresses)).unwrap(),
+            head
+        );
+
+        // Reopening the log continues the chain
+        let audit_log = AuditLog::open(&path, operator).unwrap();
+        let entry = audit_log
+            .append(AuditEvent::AuctionCancelled {
+                chain_id: 1,
+                auction_id: "auction2".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(entry.sequence, 3);
+        assert_eq!(entry.prev_hash, head.last_hash);
+        assert!(entry.verify_signature());
+
+        // An earlier head is still contained in the grown log
+        assert_eq!(verify_log(&path, Some(&head), None).unwrap().entry_count, 4);
+        remove_log(&path);
+    }
+
+    #[tokio::test]
+    async fn test_heads_are_signed_periodically() {
+        let path = temp_log_path("heads");
+        let audit_log = AuditLog::open(&path, operator())
+            .unwrap()
+            .with_head_interval(2);
+        for chain_id in 1..=5 {
+            audit_log
+                .append(AuditEvent::ChainRegistered { chain_id })
+                .await
+                .unwrap();
+        }
+
+        let heads = read_heads(heads_path(&path)).unwrap();
+        let entry_counts: Vec<u64> = heads.iter().map(|head| head.head.entry_count).collect();
+        assert_eq!(entry_counts, vec![2, 4]);
+        assert!(heads.iter().all(SignedAuditHead::verify_signature));
+
+        let mut forged_head = heads[1].clone();
+        forged_head.head.entry_count = 1;
+        assert!(!forged_head.verify_signature());
+        remove_log(&path);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_appends_are_written_in_order() {
+        let path = temp_log_path("concurrent");
+        let audit_log = AuditLog::open(&path, operator())
+            .unwrap()
+            .with_head_interval(4);
+        let appends: Vec<_> = (1..=16)
+            .map(|chain_id| {
+                let audit_log = audit_log.clone();
+                tokio::spawn(async move {
+                    audit_log
+                        .append(AuditEvent::ChainRegistered { chain_id })
+                        .await
+                })
+            })
+            .collect();
+        for append in appends {
+            append.await.unwrap().unwrap();
+        }
+
+        let head = audit_log.sign_head().await.unwrap().head;
+        assert_eq!(head.entry_count, 16);
+        assert_eq!(audit_log.head().await, head);
+        assert_eq!(verify_log(&path, Some(&head), None).unwrap(), head);
+        let entry_counts: Vec<u64> = read_heads(heads_path(&path))
+            .unwrap()
+            .iter()
+            .map(|head| head.head.entry_count)
+            .collect();
+        assert_eq!(entry_counts, vec![4, 8, 12, 16, 16]);
+        remove_log(&path);
+    }
+
+    #[tokio::test]
+    async fn test_partial_last_line_is_recovered_on_open() {
+        let path = temp_log_path("partial");
+        let operator = operator();
+        let head = write_log(&path, operator.clone()).await;
+
+        // A crash in the middle of a write leaves part of a line behind
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, format!("{}{{\"sequence\":3,", contents)).unwrap();
+        assert!(matches!(
+            verify_log(&path, None, None),
+            Err(AuditError::MalformedEntry(4))
+        ));
+
+        let audit_log = AuditLog::open(&path, operator).unwrap();
+        assert_eq!(audit_log.head().await, head);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        let entry = audit_log
+            .append(AuditEvent::ChainRegistered { chain_id: 2 })
+            .await
+            .unwrap();
+        assert_eq!(entry.prev_hash, head.last_hash);
+        assert_eq!(verify_log(&path, None, None).unwrap().entry_count, 4);
+        remove_log(&path);
+    }
+
+    #[tokio::test]
+    async fn test_verifier_detects_edits_and_truncation() {
+        let path = temp_log_path("tamper");
+        let operator = operator();
+        write_log(&path, operator.clone()).await;
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+
+        // Editing an entry
+        std::fs::write(&path, contents.replace("auction1", "auction3")).unwrap();
+        assert!(matches!(
+            verify_log(&path, None, None),
+            Err(AuditError::BrokenChain(1))
+        ));
+
+        // Removing an entry from the middle
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(matches!(
+            verify_log(&path, None, None),
+            Err(AuditError::BrokenChain(1))
+        ));
+
+        // Cutting entries off the end is caught by the signed head persisted next to the log
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        assert!(matches!(
+            AuditLog::open(&path, operator.clone()),
+            Err(AuditError::Truncated {
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        // Rebuilding a consistent chain takes the operator key
+        std::fs::remove_file(heads_path(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        write_log(&path, self::operator()).await;
+        assert!(verify_log(&path, None, None).is_ok());
+        assert!(matches!(
+            verify_log(&path, None, Some(&[operator.address().await])),
+            Err(AuditError::InvalidSignature(0))
+        ));
+
+        // A signature taken from another entry does not verify
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut entry: AuditEntry = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        entry.operator_signature =
+            serde_json::from_str::<AuditEntry>(contents.lines().nth(1).unwrap())
+                .unwrap()
+                .operator_signature;
+        let rest: Vec<&str> = contents.lines().skip(1).collect();
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n",
+                serde_json::to_string(&entry).unwrap(),
+                rest.join("\n")
+            ),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_log(&path, None, None),
+            Err(AuditError::InvalidSignature(0))
+        ));
+        remove_log(&path);
+    }
+}
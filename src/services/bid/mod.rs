@@ -1,28 +1,53 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use tokio::{
-    sync::{Mutex, RwLock},
+    sync::{broadcast::error::RecvError, Mutex, RwLock},
     task::JoinHandle,
     time::{self, Duration},
 };
 
 use crate::{
     core::{
-        domain::{AuctionId, Bid, ChainId},
+        domain::{
+            AuctionCancellation, AuctionEvent, AuctionId, AuctionMode, Bid, BidCancellation,
+            BidLimits, BidReceipt, BidRemoval, ChainId, RemovalReason, TieBreakRule,
+        },
         AuctionManager,
     },
+    services::audit::AuditEvent,
     utils::{
         errors::{AuctionError, BidError},
+        helpers::current_unix_ms,
         types::{ArcMutexHashMap, ArcRwLockHashMap},
     },
 };
 
+/// How long before an auction's deadline the final flush of its buffered bids happens.
+const FLUSH_DEADLINE_MARGIN_MS: u64 = 100;
+
+/// How often buffered bids are flushed once an auction is within `FLUSH_DEADLINE_MARGIN_MS` of
+/// its deadline.
+const LATE_FLUSH_DELAY_MS: u64 = 10;
+
 /// BidService manages bids across multiple chains and periodically flushes them.
 #[derive(Clone)]
 pub struct BidService {
     /// Stores bids for each chain, protected by locks for thread safety.
     bid_buffer: ArcRwLockHashMap<ChainId, ArcMutexHashMap<AuctionId, Vec<Bid>>>,
 
+    /// Tracks the next arrival sequence number for each auction, shared with the
+    /// `AuctionManager`, whose workers stamp the bids submitted to them directly.
+    bid_sequences: ArcMutexHashMap<AuctionId, u64>,
+
+    /// Receipted bids removed from the buffer (evicted, replaced, or cancelled) for each auction.
+    /// They are handed to the auction with its next flush, so that its logs account for every
+    /// receipt.
+    buffer_removals: ArcMutexHashMap<AuctionId, Vec<BidRemoval>>,
+
     /// Specifies flush intervals for each chain.
     flush_intervals: ArcRwLockHashMap<ChainId, Duration>,
 
@@ -52,6 +77,8 @@ impl BidService {
         // Create the BidService instance.
         BidService {
             bid_buffer,
+            bid_sequences: Arc::clone(&auction_manager.bid_sequences),
+            buffer_removals: Arc::new(Mutex::new(HashMap::new())),
             flush_intervals,
             auction_manager,
         }
@@ -76,7 +103,8 @@ impl BidService {
 
             let handle = tokio::spawn(async move {
                 loop {
-                    time::sleep(interval).await;
+                    let delay = service_clone.next_flush_delay(chain_id, interval).await;
+                    time::sleep(delay).await;
 
                     if let Err(e) = service_clone
                         .flush_bids(chain_id, &bid_buffer_clone, &auction_manager_clone)
@@ -90,17 +118,67 @@ impl BidService {
             handles.push(handle);
         }
 
+        // Spawn a task that drops the buffers and arrival sequences of closed auctions.
+        let mut events = self.auction_manager.subscribe_events();
+        let service_clone = service.clone();
+        handles.push(tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(
+                        AuctionEvent::Cancelled {
+                            chain_id,
+                            auction_id,
+                        }
+                        | AuctionEvent::Ended {
+                            chain_id,
+                            auction_id,
+                        },
+                    ) => service_clone.drop_auction_bids(chain_id, &auction_id).await,
+                    // The missed events may have closed auctions, so check them all
+                    Err(RecvError::Lagged(skipped)) => {
+                        eprintln!("Missed {} auction events; resyncing buffers.", skipped);
+                        service_clone.resync_auction_bids().await;
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }));
+
         handles
     }
 
     /// Stores a bid for a specific chain and auction.
     ///
-    /// Adds the bid to the appropriate buffer for future processing.
-    pub async fn store_bid(&self, bid: Bid) -> Result<(), AuctionError> {
+    /// Rejects the bid unless `bidder_signature` is the bidder's signature over
+    /// `Bid::signing_hash`, under the chain's `SignatureScheme`. Stamps the bid with its
+    /// per-auction arrival sequence number and arrival time, which are used to break ties between
+    /// equal bids, and adds it to the appropriate buffer for future processing. A bid with a
+    /// higher nonce replaces the bidder's buffered bids. The chain's `BidLimits` bound the buffer;
+    /// when a cap is hit, the bid either displaces a worse buffered bid or is rejected.
+    ///
+    /// Returns a `BidReceipt` of the stored bid signed by the operator, which the bidder can use
+    /// to dispute an outcome that left the bid out.
+    pub async fn store_bid(&self, mut bid: Bid) -> Result<BidReceipt, AuctionError> {
+        let signature_scheme = self
+            .auction_manager
+            .get_signature_scheme(bid.chain_id)
+            .await;
+        if !signature_scheme.verifier().verify(
+            &bid.bidder_addr,
+            &bid.signing_hash(),
+            &bid.bidder_signature,
+        ) {
+            return Err(AuctionError::InvalidBuyerSignature);
+        }
+        if bid.price_per_gas == Some(0) {
+            return Err(AuctionError::ZeroPricePerGas);
+        }
+
         let chain_id = bid.chain_id;
         let auction_id = bid.auction_id.clone();
+        let bid_limits = self.auction_manager.get_bid_limits(chain_id).await;
 
-        {
+        let receipt = {
             // Acquire a read lock for the bid buffer.
             let buffer_guard = self.bid_buffer.read().await;
 
@@ -108,52 +186,263 @@ impl BidService {
             if let Some(chain_buffer_mutex) = buffer_guard.get(&chain_id) {
                 let mut chain_buffer = chain_buffer_mutex.lock().await;
 
+                // Stamp the bid with its would-be arrival order while holding the chain buffer
+                // lock, so that the caps rank it by when it actually arrived. The sequence number
+                // is only consumed once the bid is accepted.
+                let mut sequences_guard = self.bid_sequences.lock().await;
+                let next_sequence = sequences_guard.entry(auction_id.clone()).or_insert(0);
+                bid.sequence = *next_sequence;
+                bid.arrival_time = current_unix_ms();
+
+                let auction_bids = chain_buffer
+                    .entry(auction_id.clone())
+                    .or_insert_with(Vec::new);
+                let evictions = evictions_for(auction_bids, &bid, bid_limits)?;
+
+                // Sign the receipt before anything is evicted, so that a failure leaves the
+                // buffer as is.
+                let receipt = self
+                    .auction_manager
+                    .operator
+                    .with_active_key(|operator_key| BidReceipt::new(&bid, operator_key))
+                    .await
+                    .map_err(|e| AuctionError::ReceiptSigningFailed(e.to_string()))?;
+                *next_sequence += 1;
+
                 // Add the bid to the auction's buffer.
-                let auction_bids = chain_buffer.entry(auction_id).or_insert_with(Vec::new);
+                let removals = evict(auction_bids, evictions);
+                if !removals.is_empty() {
+                    self.buffer_removals
+                        .lock()
+                        .await
+                        .entry(auction_id)
+                        .or_default()
+                        .extend(removals);
+                }
                 auction_bids.push(bid);
+                receipt
             } else {
                 // Return an error if the specified chain does not exist.
                 return Err(AuctionError::InvalidChainId(chain_id));
             }
+        };
+
+        self.auction_manager
+            .record_audit_event(AuditEvent::BidAccepted {
+                chain_id: receipt.chain_id,
+                auction_id: receipt.auction_id.clone(),
+                bidder_addr: receipt.bidder_addr.clone(),
+                bid_hash: receipt.bid_hash.clone(),
+                sequence: receipt.sequence,
+            })
+            .await;
+        Ok(receipt)
+    }
+
+    /// Withdraws a bidder's bid, whether it is still buffered or already with the auction worker.
+    ///
+    /// Buffered bids of the bidder with a nonce up to the cancellation's nonce are dropped before
+    /// the cancellation is forwarded, so that they cannot be flushed to the worker afterwards.
+    /// The cancellation must be signed by the bidder.
+    pub async fn cancel_bid(&self, cancellation: BidCancellation) -> Result<String, AuctionError> {
+        let chain_id = cancellation.chain_id;
+        let auction_id = cancellation.auction_id.clone();
+
+        // Checked here as well, since the buffered bids are dropped before the worker sees it
+        let signature_scheme = self.auction_manager.get_signature_scheme(chain_id).await;
+        if !signature_scheme.verifier().verify(
+            &cancellation.bidder_addr,
+            &cancellation.signing_hash(),
+            &cancellation.bidder_signature,
+        ) {
+            return Err(AuctionError::InvalidBuyerSignature);
         }
 
-        Ok(())
+        let dropped_buffered = {
+            let buffer_guard = self.bid_buffer.read().await;
+            let chain_buffer_mutex = buffer_guard
+                .get(&chain_id)
+                .ok_or(AuctionError::InvalidChainId(chain_id))?;
+            let mut chain_buffer = chain_buffer_mutex.lock().await;
+            match chain_buffer.get_mut(&auction_id) {
+                Some(auction_bids) => {
+                    let (cancelled, kept): (Vec<Bid>, Vec<Bid>) =
+                        std::mem::take(auction_bids).into_iter().partition(|b| {
+                            b.bidder_addr == cancellation.bidder_addr
+                                && b.nonce <= cancellation.nonce
+                        });
+                    *auction_bids = kept;
+                    if !cancelled.is_empty() {
+                        self.buffer_removals
+                            .lock()
+                            .await
+                            .entry(auction_id.clone())
+                            .or_default()
+                            .extend(cancelled.iter().map(|b| BidRemoval {
+                                submission_hash: b.hash(),
+                                reason: RemovalReason::Cancelled,
+                            }));
+                    }
+                    !cancelled.is_empty()
+                }
+                None => false,
+            }
+        };
+
+        let event = AuditEvent::BidCancelled {
+            chain_id,
+            auction_id: auction_id.clone(),
+            bidder_addr: cancellation.bidder_addr.clone(),
+            nonce: cancellation.nonce,
+        };
+        let ack = match self
+            .auction_manager
+            .cancel_bid(chain_id, auction_id.clone(), cancellation)
+            .await
+        {
+            Err(AuctionError::BidNotFound(_)) if dropped_buffered => Ok(format!(
+                "ACK: Auction {} buffered bid cancelled.",
+                auction_id
+            )),
+            result => result,
+        }?;
+        self.auction_manager.record_audit_event(event).await;
+        Ok(ack)
+    }
+
+    /// Cancels a queued or running auction on behalf of its seller, and drops the bids buffered
+    /// for it.
+    pub async fn cancel_auction(
+        &self,
+        cancellation: AuctionCancellation,
+    ) -> Result<String, AuctionError> {
+        let chain_id = cancellation.chain_id;
+        let auction_id = cancellation.auction_id.clone();
+
+        let result = self.auction_manager.cancel_auction(cancellation).await?;
+        self.drop_auction_bids(chain_id, &auction_id).await;
+        Ok(result)
+    }
+
+    /// Drops the buffered bids and the arrival sequence of an auction.
+    async fn drop_auction_bids(&self, chain_id: ChainId, auction_id: &AuctionId) {
+        {
+            let buffer_guard = self.bid_buffer.read().await;
+            if let Some(chain_buffer_mutex) = buffer_guard.get(&chain_id) {
+                chain_buffer_mutex.lock().await.remove(auction_id);
+            }
+        }
+        self.bid_sequences.lock().await.remove(auction_id);
+        self.buffer_removals.lock().await.remove(auction_id);
+    }
+
+    /// Drops the buffered bids and arrival sequences of every auction that is neither ongoing nor
+    /// queued anymore, for when the events announcing that were missed.
+    ///
+    /// All chain buffers stay locked while the open auctions are looked up, so that no bid can
+    /// be stored in between for an auction that has just been registered.
+    async fn resync_auction_bids(&self) {
+        let buffer_guard = self.bid_buffer.read().await;
+        let mut chain_ids: Vec<ChainId> = buffer_guard.keys().copied().collect();
+        chain_ids.sort_unstable();
+
+        let mut chain_buffers = Vec::with_capacity(chain_ids.len());
+        let mut open_auction_ids = HashSet::new();
+        for chain_id in chain_ids {
+            chain_buffers.push(buffer_guard[&chain_id].lock().await);
+            open_auction_ids.extend(self.auction_manager.get_ongoing_auction_id(chain_id).await);
+            open_auction_ids.extend(self.auction_manager.get_queued_auction_ids(chain_id).await);
+        }
+
+        for chain_buffer in chain_buffers.iter_mut() {
+            chain_buffer.retain(|auction_id, _| open_auction_ids.contains(auction_id));
+        }
+        self.bid_sequences
+            .lock()
+            .await
+            .retain(|auction_id, _| open_auction_ids.contains(auction_id));
+        self.buffer_removals
+            .lock()
+            .await
+            .retain(|auction_id, _| open_auction_ids.contains(auction_id));
     }
 
     /// Flushes bids for a specific chain by sending them to the AuctionManager.
     ///
-    /// Collects bids for the ongoing auction and submits them in a batch.
+    /// Collects bids for the ongoing auction and submits them in a batch. On sealed-bid chains,
+    /// bids for queued auctions are flushed as well, so that the manager can weigh them against
+    /// package bids. It validates them as it would for the ongoing auction, caps included.
     async fn flush_bids(
         &self,
         chain_id: ChainId,
         bid_buffer: &ArcRwLockHashMap<ChainId, ArcMutexHashMap<AuctionId, Vec<Bid>>>,
         auction_manager: &Arc<AuctionManager>,
     ) -> Result<(), BidError> {
-        // Retrieve the ongoing auction ID for the chain.
-        let auction_id = match auction_manager.get_ongoing_auction_id(chain_id).await {
-            Some(auction_id) => auction_id,
-            None => return Ok(()),
-        };
+        // Retrieve the ongoing auction ID for the chain, followed by the queued ones.
+        let mut auction_ids: Vec<AuctionId> = auction_manager
+            .get_ongoing_auction_id(chain_id)
+            .await
+            .into_iter()
+            .collect();
+        if auction_manager.get_auction_mode(chain_id).await == AuctionMode::SealedBid {
+            auction_ids.extend(auction_manager.get_queued_auction_ids(chain_id).await);
+        }
 
-        // Collect and remove bids associated with the ongoing auction.
-        let bids_to_flush = {
-            let buffer_guard = bid_buffer.read().await;
-            let chain_buffer_mutex = match buffer_guard.get(&chain_id) {
-                Some(mutex) => mutex,
-                None => return Err(BidError::InvalidChainId(chain_id)),
+        for auction_id in auction_ids {
+            // Collect and remove bids associated with the auction, along with the receipted bids
+            // removed from its buffer.
+            let (bids_to_flush, removals) = {
+                let buffer_guard = bid_buffer.read().await;
+                let chain_buffer_mutex = match buffer_guard.get(&chain_id) {
+                    Some(mutex) => mutex,
+                    None => return Err(BidError::InvalidChainId(chain_id)),
+                };
+                let mut chain_buffer = chain_buffer_mutex.lock().await;
+                let Some(bids) = chain_buffer.remove(&auction_id) else {
+                    continue;
+                };
+                let removals = self
+                    .buffer_removals
+                    .lock()
+                    .await
+                    .remove(&auction_id)
+                    .unwrap_or_default();
+                (bids, removals)
             };
-            let mut chain_buffer = chain_buffer_mutex.lock().await;
-            match chain_buffer.remove(&auction_id) {
-                Some(bids) => bids.clone(),
-                None => return Ok(()),
-            }
-        };
 
-        // Submit the collected bids to the AuctionManager.
-        auction_manager
-            .submit_bid_batch(chain_id, auction_id.clone(), bids_to_flush)
+            // Submit the collected bids to the AuctionManager.
+            auction_manager
+                .submit_bid_batch(chain_id, auction_id, bids_to_flush, removals)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns how long the flush task of a chain should sleep before flushing again.
+    ///
+    /// Normally this is the chain's flush interval, but if the ongoing auction's deadline
+    /// (including soft-close extensions) comes sooner, the task wakes up shortly before it
+    /// so that buffered bids still reach the worker in time. Within that last margin it
+    /// flushes every `LATE_FLUSH_DELAY_MS`, so that bids arriving at the last moment are not
+    /// held back past the deadline.
+    async fn next_flush_delay(&self, chain_id: ChainId, interval: Duration) -> Duration {
+        let Some(end_time) = self
+            .auction_manager
+            .get_ongoing_auction_end_time(chain_id)
             .await
-            .map_err(|e| e.into())
+        else {
+            return interval;
+        };
+
+        let remaining_ms = end_time
+            .saturating_sub(current_unix_ms())
+            .saturating_sub(FLUSH_DEADLINE_MARGIN_MS);
+        if remaining_ms == 0 {
+            return interval.min(Duration::from_millis(LATE_FLUSH_DELAY_MS));
+        }
+
+        interval.min(Duration::from_millis(remaining_ms))
     }
 
     /// Adds a new chain to the BidService with a specified flush interval.
@@ -180,7 +469,8 @@ impl BidService {
 
         tokio::spawn(async move {
             loop {
-                time::sleep(interval).await;
+                let delay = service_clone.next_flush_delay(chain_id, interval).await;
+                time::sleep(delay).await;
 
                 if let Err(e) = service_clone
                     .flush_bids(chain_id, &bid_buffer, &auction_manager)
@@ -192,3 +482,263 @@ impl BidService {
         });
     }
 }
+
+/// Enforces the chain's `BidLimits` on an auction's buffered bids before `bid` is added, and
+/// returns the indices of the bids to remove, with the reasons (see `evict`).
+///
+/// As in the worker, `bid` replaces the bidder's buffered bids if its nonce is higher, and is
+/// rejected otherwise. Replaced bids do not count towards the caps, so that a bidder at the cap
+/// can still lower their bid. For each cap that is still hit, the worst bid in its scope is
+/// evicted if `bid` ranks better than it; otherwise `bid` is rejected.
+fn evictions_for(
+    auction_bids: &[Bid],
+    bid: &Bid,
+    bid_limits: BidLimits,
+) -> Result<Vec<(usize, RemovalReason)>, AuctionError> {
+    let mut evictions = Vec::new();
+    for (index, b) in auction_bids.iter().enumerate() {
+        if b.bidder_addr != bid.bidder_addr {
+            continue;
+        }
+        if b.nonce >= bid.nonce {
+            return Err(AuctionError::StaleBidNonce {
+                nonce: bid.nonce,
+                current_nonce: b.nonce,
+            });
+        }
+        evictions.push((index, RemovalReason::Replaced));
+    }
+
+    let rule = TieBreakRule::default();
+    let is_evicted = |evictions: &[(usize, RemovalReason)], index: usize| {
+        evictions.iter().any(|(evicted, _)| *evicted == index)
+    };
+    // The worst bid in scope that ranks below `bid`, ignoring bids already marked for eviction
+    let worst_of = |bids: &[Bid], same_bidder: bool, evictions: &[(usize, RemovalReason)]| {
+        bids.iter()
+            .enumerate()
+            .filter(|(index, _)| !is_evicted(evictions, *index))
+            .filter(|(_, b)| !same_bidder || b.bidder_addr == bid.bidder_addr)
+            .max_by(|(_, a), (_, b)| rule.compare(a, b))
+            .filter(|(_, worst)| rule.compare(bid, worst) == Ordering::Less)
+            .map(|(index, _)| index)
+    };
+
+    if let Some(limit) = bid_limits.max_bids_per_bidder {
+        let from_bidder = auction_bids
+            .iter()
+            .enumerate()
+            .filter(|(index, b)| {
+                !is_evicted(&evictions, *index) && b.bidder_addr == bid.bidder_addr
+            })
+            .count();
+        if from_bidder >= limit {
+            let index = worst_of(auction_bids, true, &evictions)
+                .ok_or(AuctionError::BidderBidLimitReached { limit })?;
+            evictions.push((index, RemovalReason::Evicted));
+        }
+    }
+    if let Some(limit) = bid_limits.max_bids_per_auction {
+        if auction_bids.len() - evictions.len() >= limit {
+            let index = worst_of(auction_bids, false, &evictions)
+                .ok_or(AuctionError::AuctionBidLimitReached { limit })?;
+            evictions.push((index, RemovalReason::Evicted));
+        }
+    }
+
+    Ok(evictions)
+}
+
+/// Removes the bids at the indices returned by `evictions_for`, and returns their removals.
+fn evict(
+    auction_bids: &mut Vec<Bid>,
+    mut evictions: Vec<(usize, RemovalReason)>,
+) -> Vec<BidRemoval> {
+    // Remove from the back so that the remaining indices stay valid
+    evictions.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    evictions
+        .into_iter()
+        .map(|(index, reason)| BidRemoval {
+            submission_hash: auction_bids.remove(index).hash(),
+            reason,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::domain::{ChainInfo, SignatureScheme},
+        services::{
+            operator::{OperatorService, DEFAULT_ROTATION_OVERLAP_MS},
+            registry::RegistryService,
+        },
+        utils::signing::{Ed25519Key, Secp256k1Key},
+    };
+
+    fn bid(bidder_addr: &str, bid_amount: u64, sequence: u64) -> Bid {
+        Bid {
+            chain_id: 1,
+            auction_id: "auction".to_string(),
+            bidder_addr: bidder_addr.to_string(),
+            bid_amount,
+            sequence,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `BidService` for chain 1, registered with `chain_info`.
+    async fn setup(chain_info: ChainInfo) -> BidService {
+        let registry_service = {
+            let (auction_registry, chain_registry) = RegistryService::create_registry().await;
+            RegistryService::new(auction_registry, chain_registry)
+        };
+        registry_service
+            .register_chain(1, chain_info)
+            .await
+            .unwrap();
+        let auction_manager = Arc::new(
+            AuctionManager::new(
+                &registry_service,
+                OperatorService::new(Secp256k1Key::generate(), DEFAULT_ROTATION_OVERLAP_MS),
+            )
+            .await,
+        );
+        BidService::new(
+            auction_manager,
+            HashMap::from([(1, Duration::from_millis(1000))]),
+        )
+        .await
+    }
+
+    /// Creates a bid signed by `key`.
+    fn signed_bid(key: &Secp256k1Key, bid_amount: u64, sequence: u64) -> Bid {
+        let mut bid = bid(&key.address(), bid_amount, sequence);
+        bid.bidder_signature = key.sign(&bid.signing_hash()).unwrap();
+        bid
+    }
+
+    fn make_room_for(
+        auction_bids: &mut Vec<Bid>,
+        bid: &Bid,
+        bid_limits: BidLimits,
+    ) -> Result<(), AuctionError> {
+        let evictions = evictions_for(auction_bids, bid, bid_limits)?;
+        evict(auction_bids, evictions);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bid_caps_keep_best_buffered_bids() {
+        let bid_limits = BidLimits {
+            max_bids_per_bidder: Some(1),
+            max_bids_per_auction: Some(2),
+        };
+        let with_nonce = |bid: Bid, nonce: u64| Bid { nonce, ..bid };
+        let mut auction_bids = vec![with_nonce(bid("0xBidder1", 200, 0), 1)];
+
+        // A newer nonce replaces the bidder's bid even at the cap, e.g., to lower it
+        let lower = with_nonce(bid("0xBidder1", 100, 1), 2);
+        make_room_for(&mut auction_bids, &lower, bid_limits).unwrap();
+        auction_bids.push(lower);
+        let result = make_room_for(
+            &mut auction_bids,
+            &with_nonce(bid("0xBidder1", 300, 2), 2),
+            bid_limits,
+        );
+        assert!(matches!(
+            result,
+            Err(AuctionError::StaleBidNonce {
+                nonce: 2,
+                current_nonce: 2
+            })
+        ));
+        let amounts: Vec<u64> = auction_bids.iter().map(|b| b.bid_amount).collect();
+        assert_eq!(amounts, vec![100]);
+
+        let other = bid("0xBidder2", 150, 3);
+        make_room_for(&mut auction_bids, &other, bid_limits).unwrap();
+        auction_bids.push(other);
+
+        // The auction is full, so only a bid better than the worst one gets in
+        let result = make_room_for(&mut auction_bids, &bid("0xBidder3", 90, 4), bid_limits);
+        assert!(matches!(
+            result,
+            Err(AuctionError::AuctionBidLimitReached { limit: 2 })
+        ));
+        make_room_for(&mut auction_bids, &bid("0xBidder3", 250, 5), bid_limits).unwrap();
+        assert_eq!(auction_bids.len(), 1);
+        assert_eq!(auction_bids[0].bidder_addr, "0xBidder2");
+    }
+
+    #[tokio::test]
+    async fn test_caps_rank_bids_by_actual_arrival() {
+        let bid_service = setup(ChainInfo {
+            bid_limits: BidLimits {
+                max_bids_per_bidder: None,
+                max_bids_per_auction: Some(1),
+            },
+            ..Default::default()
+        })
+        .await;
+
+        let honest = bid_service
+            .store_bid(signed_bid(&Secp256k1Key::generate(), 1000, 0))
+            .await
+            .unwrap();
+        assert_eq!(honest.sequence, 0);
+
+        // An equal bid claiming an earlier arrival still ranks after the one buffered first
+        let result = bid_service
+            .store_bid(signed_bid(&Secp256k1Key::generate(), 1000, 0))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AuctionError::AuctionBidLimitReached { limit: 1 })
+        ));
+
+        // The rejected bid did not consume a sequence number
+        let better = bid_service
+            .store_bid(signed_bid(&Secp256k1Key::generate(), 1100, 0))
+            .await
+            .unwrap();
+        assert_eq!(better.sequence, 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_bid_checks_signature_under_chain_scheme() {
+        let bid_service = setup(ChainInfo {
+            signature_scheme: SignatureScheme::Ed25519,
+            ..Default::default()
+        })
+        .await;
+
+        let ed25519_key = Ed25519Key::generate();
+        let mut ed25519_bid = bid(&ed25519_key.address(), 1000, 0);
+        ed25519_bid.bidder_signature = ed25519_key.sign(&ed25519_bid.signing_hash());
+        bid_service.store_bid(ed25519_bid).await.unwrap();
+
+        // An Ethereum-style account cannot bid on an Ed25519 chain
+        let result = bid_service
+            .store_bid(signed_bid(&Secp256k1Key::generate(), 1000, 0))
+            .await;
+        assert!(matches!(result, Err(AuctionError::InvalidBuyerSignature)));
+    }
+
+    #[tokio::test]
+    async fn test_resync_drops_bids_of_closed_auctions() {
+        let bid_service = setup(ChainInfo::default()).await;
+
+        // The auction is not ongoing or queued, e.g., because it ended while events were missed
+        bid_service
+            .store_bid(signed_bid(&Secp256k1Key::generate(), 1000, 0))
+            .await
+            .unwrap();
+
+        bid_service.resync_auction_bids().await;
+        let buffer_guard = bid_service.bid_buffer.read().await;
+        assert!(buffer_guard[&1].lock().await.is_empty());
+        assert!(bid_service.bid_sequences.lock().await.is_empty());
+    }
+}
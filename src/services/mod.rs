@@ -1,2 +1,4 @@
+pub mod audit;
 pub mod bid;
+pub mod operator;
 pub mod registry;
@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{errors::KeystoreError, signing::Secp256k1Key};
+
+/// The scrypt cost (`log2(N)`) of newly created keystores.
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 15;
+
+const KEYSTORE_VERSION: u32 = 1;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// An operator key encrypted under a password, as stored on disk (JSON).
+///
+/// The encryption key is derived from the password with scrypt, and the secret key is sealed
+/// with ChaCha20-Poly1305. The address stays in the clear, so that a keystore can be identified
+/// without its password, and is authenticated as associated data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub address: String,
+    pub scrypt_log_n: u8,
+    /// Hex-encoded scrypt salt.
+    pub salt: String,
+    /// Hex-encoded 12-byte ChaCha20-Poly1305 nonce.
+    pub nonce: String,
+    /// Hex-encoded encrypted secret key.
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypts a key under a password. `scrypt_log_n` is the cost of deriving the encryption key
+    /// (see `DEFAULT_SCRYPT_LOG_N`).
+    pub fn encrypt(
+        key: &Secp256k1Key,
+        password: &str,
+        scrypt_log_n: u8,
+    ) -> Result<Self, KeystoreError> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+
+        let address = key.address();
+        let cipher = cipher(password, &salt, scrypt_log_n)?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &key.secret_key_bytes(),
+                    aad: address.as_bytes(),
+                },
+            )
+            .map_err(|_| KeystoreError::InvalidFormat("failed to encrypt key".to_string()))?;
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            address,
+            scrypt_log_n,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the key and checks that it matches the keystore's address.
+    pub fn decrypt(&self, password: &str) -> Result<Secp256k1Key, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::InvalidFormat(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+
+        let salt = decode(&self.salt)?;
+        let nonce: [u8; 12] = decode(&self.nonce)?
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidFormat("expected a 12-byte nonce".to_string()))?;
+        let ciphertext = decode(&self.ciphertext)?;
+
+        let cipher = cipher(password, &salt, self.scrypt_log_n)?;
+        let secret_key = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: self.address.as_bytes(),
+                },
+            )
+            .map_err(|_| KeystoreError::DecryptionFailed)?;
+
+        let key = Secp256k1Key::from_hex(&hex::encode(secret_key))
+            .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?;
+        if !key.address().eq_ignore_ascii_case(&self.address) {
+            return Err(KeystoreError::AddressMismatch(self.address.clone()));
+        }
+        Ok(key)
+    }
+
+    /// Reads a keystore from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeystoreError> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| KeystoreError::InvalidFormat(e.to_string()))
+    }
+
+    /// Writes the keystore to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), KeystoreError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Derives the symmetric cipher from the password.
+fn cipher(
+    password: &str,
+    salt: &[u8],
+    scrypt_log_n: u8,
+) -> Result<ChaCha20Poly1305, KeystoreError> {
+    let params = scrypt::Params::new(scrypt_log_n, SCRYPT_R, SCRYPT_P, 32)
+        .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(value).map_err(|e| KeystoreError::InvalidFormat(e.to_string()))
+}
@@ -0,0 +1,220 @@
+pub mod keystore;
+
+use std::{path::Path, sync::Arc};
+
+pub use keystore::Keystore;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::utils::{
+    errors::{KeystoreError, SignatureError},
+    helpers::current_unix_ms,
+    signing::{self, Secp256k1Key},
+    types::ArcRwLock,
+};
+
+/// How long a rotated-out operator key stays valid for verification by default.
+pub const DEFAULT_ROTATION_OVERLAP_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// A published operator key. Signatures by a retired key remain valid until `valid_until`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorPublicKey {
+    pub address: String,
+    /// Hex-encoded compressed secp256k1 public key.
+    pub public_key: String,
+    /// `None` for the active key.
+    pub valid_until: Option<u64>,
+}
+
+/// `OperatorService` holds the identity of the Lightbulb operator: the key that signs what
+/// Lightbulb emits (e.g., `AuctionResult`s), loaded from an encrypted `Keystore`.
+///
+/// Keys are rotated in a planned way. The new key signs from the moment it is installed, while
+/// the previous key stays valid for verification during an overlap window, so that outcomes
+/// signed shortly before the rotation can still be checked against the published keys.
+#[derive(Clone)]
+pub struct OperatorService {
+    /// The key that signs new messages.
+    active_key: ArcRwLock<Secp256k1Key>,
+
+    /// Rotated-out keys, each with the end of its overlap window.
+    retired_keys: ArcRwLock<Vec<OperatorPublicKey>>,
+
+    /// How long a rotated-out key stays valid for verification.
+    rotation_overlap_ms: u64,
+}
+
+impl OperatorService {
+    /// Creates a new `OperatorService` signing with the given key.
+    pub fn new(key: Secp256k1Key, rotation_overlap_ms: u64) -> Self {
+        OperatorService {
+            active_key: Arc::new(RwLock::new(key)),
+            retired_keys: Arc::new(RwLock::new(Vec::new())),
+            rotation_overlap_ms,
+        }
+    }
+
+    /// Creates a new `OperatorService` signing with the key of an encrypted keystore file.
+    pub fn from_keystore(
+        path: impl AsRef<Path>,
+        password: &str,
+        rotation_overlap_ms: u64,
+    ) -> Result<Self, KeystoreError> {
+        let key = Keystore::load(path)?.decrypt(password)?;
+        Ok(Self::new(key, rotation_overlap_ms))
+    }
+
+    /// Returns the address of the active key.
+    pub async fn address(&self) -> String {
+        self.active_key.read().await.address()
+    }
+
+    /// Signs a 32-byte digest with the active key and returns the signer's address along with
+    /// the signature.
+    pub async fn sign(&self, digest: &[u8; 32]) -> Result<(String, String), SignatureError> {
+        let active_key = self.active_key.read().await;
+        Ok((active_key.address(), active_key.sign(digest)?))
+    }
+
+    /// Runs `f` with the active key, e.g., to create a message that names its signer. The key
+    /// cannot be rotated while `f` runs.
+    pub async fn with_active_key<T>(&self, f: impl FnOnce(&Secp256k1Key) -> T) -> T {
+        let active_key = self.active_key.read().await;
+        f(&active_key)
+    }
+
+    /// Installs a new active key. The previous key stays valid for verification until the
+    /// overlap window has passed.
+    pub async fn rotate(&self, new_key: Secp256k1Key) {
+        let mut active_key = self.active_key.write().await;
+        let previous_key = std::mem::replace(&mut *active_key, new_key);
+
+        let now = current_unix_ms();
+        let mut retired_keys = self.retired_keys.write().await;
+        retired_keys.retain(|key| key.valid_until.is_some_and(|valid_until| valid_until > now));
+        retired_keys.push(OperatorPublicKey {
+            address: previous_key.address(),
+            public_key: previous_key.public_key(),
+            valid_until: Some(now + self.rotation_overlap_ms),
+        });
+        println!(
+            "[Operator] Rotated key {} to {}.",
+            previous_key.address(),
+            active_key.address()
+        );
+    }
+
+    /// Rotates to the key of an encrypted keystore file.
+    pub async fn rotate_from_keystore(
+        &self,
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<(), KeystoreError> {
+        let key = Keystore::load(path)?.decrypt(password)?;
+        self.rotate(key).await;
+        Ok(())
+    }
+
+    /// Returns the keys that signatures are currently accepted from: the active key first,
+    /// followed by the retired keys whose overlap window has not passed.
+    pub async fn public_keys(&self) -> Vec<OperatorPublicKey> {
+        let now = current_unix_ms();
+        let active_key = {
+            let active_key = self.active_key.read().await;
+            OperatorPublicKey {
+                address: active_key.address(),
+                public_key: active_key.public_key(),
+                valid_until: None,
+            }
+        };
+
+        let retired_keys = self.retired_keys.read().await;
+        std::iter::once(active_key)
+            .chain(
+                retired_keys
+                    .iter()
+                    .filter(|key| key.valid_until.is_some_and(|valid_until| valid_until > now))
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    /// Checks whether an address belongs to the active key or to a retired key that is still
+    /// within its overlap window.
+    pub async fn is_valid_signer(&self, address: &str) -> bool {
+        self.public_keys()
+            .await
+            .iter()
+            .any(|key| key.address.eq_ignore_ascii_case(address))
+    }
+
+    /// Checks that a signature over a digest was made by a currently valid operator key.
+    pub async fn verify(&self, digest: &[u8; 32], signature: &str) -> bool {
+        match signing::recover_address(digest, signature) {
+            Ok(signer) => self.is_valid_signer(&signer).await,
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::{sleep, Duration};
+
+    use super::*;
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("lightbulb-keystore-{}.json", current_unix_ms()));
+        let key = Secp256k1Key::generate();
+        Keystore::encrypt(&key, "correct horse", 4)
+            .unwrap()
+            .save(&path)
+            .unwrap();
+
+        let keystore = Keystore::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(keystore.address, key.address());
+        assert!(!keystore
+            .ciphertext
+            .contains(&hex::encode(key.secret_key_bytes())));
+        assert_eq!(
+            keystore.decrypt("correct horse").unwrap().address(),
+            key.address()
+        );
+        assert!(matches!(
+            keystore.decrypt("wrong password"),
+            Err(KeystoreError::DecryptionFailed)
+        ));
+
+        let mut tampered = keystore.clone();
+        tampered.address = Secp256k1Key::generate().address();
+        assert!(matches!(
+            tampered.decrypt("correct horse"),
+            Err(KeystoreError::DecryptionFailed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotated_key_is_valid_during_overlap() {
+        let operator = OperatorService::new(Secp256k1Key::generate(), 200);
+        let digest = [7u8; 32];
+        let (old_address, old_signature) = operator.sign(&digest).await.unwrap();
+
+        operator.rotate(Secp256k1Key::generate()).await;
+        let (new_address, new_signature) = operator.sign(&digest).await.unwrap();
+        assert_ne!(new_address, old_address);
+
+        let public_keys = operator.public_keys().await;
+        assert_eq!(public_keys.len(), 2);
+        assert_eq!(public_keys[0].address, new_address);
+        assert!(operator.verify(&digest, &old_signature).await);
+        assert!(operator.verify(&digest, &new_signature).await);
+
+        sleep(Duration::from_millis(300)).await;
+        assert_eq!(operator.public_keys().await.len(), 1);
+        assert!(!operator.verify(&digest, &old_signature).await);
+        assert!(operator.verify(&digest, &new_signature).await);
+    }
+}
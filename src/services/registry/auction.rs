@@ -7,7 +7,7 @@ use std::{
 use tokio::sync::RwLock;
 
 use crate::{
-    core::domain::{AuctionInfo, ChainId},
+    core::domain::{AuctionId, AuctionInfo, ChainId},
     services::registry::ChainRegistry,
     utils::errors::RegistryError,
 };
@@ -68,6 +68,34 @@ impl AuctionRegistry {
             .map(|reverse| &reverse.0)
     }
 
+    /// Removes a queued auction by its ID and returns it.
+    ///
+    /// Returns `None` if no such auction is queued for the chain.
+    pub fn remove_auction(
+        &mut self,
+        chain_id: ChainId,
+        auction_id: &AuctionId,
+    ) -> Option<AuctionInfo> {
+        let queue = self.auction_queues.get_mut(&chain_id)?;
+        let removed = queue
+            .iter()
+            .find(|reverse| &reverse.0.id == auction_id)
+            .map(|reverse| reverse.0.clone())?;
+        queue.retain(|reverse| &reverse.0.id != auction_id);
+        Some(removed)
+    }
+
+    /// Returns all queued auctions for the specified chain, ordered from next to last.
+    pub fn get_queued_auctions(&self, chain_id: ChainId) -> Vec<AuctionInfo> {
+        let mut queued: Vec<AuctionInfo> = self
+            .auction_queues
+            .get(&chain_id)
+            .map(|queue| queue.iter().map(|reverse| reverse.0.clone()).collect())
+            .unwrap_or_default();
+        queued.sort();
+        queued
+    }
+
     /// Registers a new chain in the auction registry.
     ///
     /// Returns an error if the chain is already registered.
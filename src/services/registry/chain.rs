@@ -1,7 +1,10 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    core::domain::{ChainId, ChainInfo},
+    core::{
+        auction::AuctionRule,
+        domain::{ChainId, ChainInfo},
+    },
     utils::errors::RegistryError,
 };
 
@@ -10,12 +13,18 @@ use crate::{
 pub struct ChainRegistry {
     /// Stores information about each registered chain, mapped by their `ChainId`.
     chain_info_map: HashMap<ChainId, ChainInfo>,
+
+    /// Stores the custom winner-selection rule of each chain that has one.
+    auction_rules: HashMap<ChainId, Arc<dyn AuctionRule>>,
 }
 
 impl ChainRegistry {
     /// Creates a new `ChainRegistry` with an initial set of chain information.
     pub fn new(chain_info_map: HashMap<ChainId, ChainInfo>) -> Self {
-        ChainRegistry { chain_info_map }
+        ChainRegistry {
+            chain_info_map,
+            auction_rules: HashMap::new(),
+        }
     }
 
     /// Registers a new chain with the specified `ChainId` and `ChainInfo`.
@@ -55,6 +64,33 @@ impl ChainRegistry {
         }
     }
 
+    /// Retrieves the `ChainInfo` registered for the specified chain, if available.
+    pub fn get_chain_info(&self, chain_id: ChainId) -> Option<&ChainInfo> {
+        self.chain_info_map.get(&chain_id)
+    }
+
+    /// Registers a custom winner-selection rule for the specified chain.
+    /// It replaces any rule registered before and applies to workers started afterwards.
+    ///
+    /// Returns an error if the chain is not registered.
+    pub fn register_auction_rule(
+        &mut self,
+        chain_id: ChainId,
+        auction_rule: Arc<dyn AuctionRule>,
+    ) -> Result<(), RegistryError> {
+        if !self.chain_info_map.contains_key(&chain_id) {
+            return Err(RegistryError::InvalidChainId(chain_id));
+        }
+
+        self.auction_rules.insert(chain_id, auction_rule);
+        Ok(())
+    }
+
+    /// Retrieves the custom winner-selection rule registered for the specified chain, if any.
+    pub fn get_auction_rule(&self, chain_id: ChainId) -> Option<Arc<dyn AuctionRule>> {
+        self.auction_rules.get(&chain_id).cloned()
+    }
+
     /// Retrieves the maximum gas limit for the specified chain, if available.
     pub fn get_max_gas_limit(&self, chain_id: ChainId) -> Option<u64> {
         self.chain_info_map
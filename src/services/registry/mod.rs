@@ -8,7 +8,11 @@ pub use chain::ChainRegistry;
 use tokio::sync::RwLock;
 
 use crate::{
-    core::domain::{AuctionInfo, ChainId, ChainInfo},
+    core::{
+        auction::AuctionRule,
+        domain::{AuctionInfo, ChainId, ChainInfo},
+    },
+    services::audit::{AuditEvent, AuditLog},
     utils::{errors::RegistryError, types::ArcRwLock},
 };
 
@@ -19,6 +23,9 @@ pub struct RegistryService {
 
     /// Stores chain-related data in a thread-safe manner.
     chain_registry: ArcRwLock<ChainRegistry>,
+
+    /// Records lifecycle events, if enabled. Shared with the `AuctionManager` created from this service.
+    audit_log: Option<AuditLog>,
 }
 
 impl RegistryService {
@@ -30,9 +37,17 @@ impl RegistryService {
         RegistryService {
             auction_registry,
             chain_registry,
+            audit_log: None,
         }
     }
 
+    /// Records chain registrations, queued auctions, and, through the `AuctionManager` and
+    /// `BidService` built on this service, the rest of the auction lifecycle in an audit log.
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
     /// Initializes new registries for chains and auctions.
     pub async fn create_registry() -> (ArcRwLock<AuctionRegistry>, ArcRwLock<ChainRegistry>) {
         let chain_registry = Arc::new(RwLock::new(ChainRegistry::default()));
@@ -46,6 +61,16 @@ impl RegistryService {
         self.auction_registry.clone()
     }
 
+    /// Provides a clone of the chain registry.
+    pub fn get_chain_registry(&self) -> ArcRwLock<ChainRegistry> {
+        self.chain_registry.clone()
+    }
+
+    /// Provides a clone of the audit log, if enabled.
+    pub fn get_audit_log(&self) -> Option<AuditLog> {
+        self.audit_log.clone()
+    }
+
     /// Retrieves a list of all registered chain IDs.
     pub async fn get_chain_ids(&self) -> Vec<ChainId> {
         let chain_registry = self.chain_registry.read().await;
@@ -67,8 +92,26 @@ impl RegistryService {
         {
             // Notify the auction registry about the new chain.
             let mut auction_registry_guard = self.auction_registry.write().await;
-            auction_registry_guard.register_chain(chain_id)
+            auction_registry_guard.register_chain(chain_id)?;
         }
+
+        if let Some(audit_log) = &self.audit_log {
+            audit_log
+                .record(AuditEvent::ChainRegistered { chain_id })
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Registers a custom winner-selection rule for a registered chain.
+    /// Must be called before the chain's `AuctionWorker` is started to take effect.
+    pub async fn register_auction_rule(
+        &self,
+        chain_id: ChainId,
+        auction_rule: Arc<dyn AuctionRule>,
+    ) -> Result<(), RegistryError> {
+        let mut chain_registry_guard = self.chain_registry.write().await;
+        chain_registry_guard.register_auction_rule(chain_id, auction_rule)
     }
 
     /// Submits new auction information after validation.
@@ -79,9 +122,22 @@ impl RegistryService {
         // Validate the auction information.
         self.validate_auction_info(&auction_info).await?;
 
+        let event = AuditEvent::AuctionQueued {
+            chain_id: auction_info.chain_id,
+            auction_id: auction_info.id.clone(),
+            seller_address: auction_info.seller_address.clone(),
+        };
+
         // Store the auction information.
-        let mut auction_registry = self.auction_registry.write().await;
-        auction_registry.store_auction_info(auction_info)
+        {
+            let mut auction_registry = self.auction_registry.write().await;
+            auction_registry.store_auction_info(auction_info)?;
+        }
+
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(event).await;
+        }
+        Ok(())
     }
 
     /// Validates the provided auction information.
@@ -103,6 +159,24 @@ impl RegistryService {
             ));
         }
 
+        // Ensure the auction was signed by the seller, under the chain's signature scheme.
+        let signature_scheme = chain_registry
+            .get_chain_info(auction_info.chain_id)
+            .map(|chain_info| chain_info.signature_scheme)
+            .unwrap_or_default();
+        if !signature_scheme.verifier().verify(
+            &auction_info.seller_address,
+            &auction_info.signing_hash(),
+            &auction_info.seller_signature,
+        ) {
+            return Err(RegistryError::InvalidSellerSignature);
+        }
+
+        // Ensure the reserve price, if set, is meaningful.
+        if auction_info.reserve_price == Some(0) {
+            return Err(RegistryError::InvalidReservePrice(0));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,522 @@
+use serde_json::{json, Value};
+
+use crate::{
+    core::domain::{AuctionInfo, Bid, ChainId, Tx},
+    utils::helpers::keccak256,
+};
+
+/// The `name` of the EIP-712 domain of every Lightbulb message.
+pub const DOMAIN_NAME: &str = "Lightbulb";
+
+/// The `version` of the EIP-712 domain of every Lightbulb message.
+pub const DOMAIN_VERSION: &str = "1";
+
+pub const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId)";
+
+pub const TX_TYPE: &str = "Tx(string txData,uint256 gasLimit)";
+
+/// `pricePerGas` is zero for bids of a fixed amount. A per-gas bid with a zero price would
+/// encode the same, so it is rejected (see `AuctionError::ZeroPricePerGas`).
+pub const BID_TYPE: &str = "Bid(uint256 chainId,string auctionId,address bidder,uint256 bidAmount,uint256 blockspaceSize,uint256 nonce,Tx[] txList,uint256 pricePerGas)";
+
+/// `BID_TYPE` for bidders whose account is a 32-byte Ed25519 public key.
+pub const ED25519_BID_TYPE: &str = "Bid(uint256 chainId,string auctionId,bytes32 bidder,uint256 bidAmount,uint256 blockspaceSize,uint256 nonce,Tx[] txList,uint256 pricePerGas)";
+
+/// `reservePrice` is zero for auctions without a reserve.
+pub const AUCTION_INFO_TYPE: &str = "AuctionInfo(uint256 chainId,uint256 blockNumber,address seller,uint256 blockspaceSize,uint256 startTime,uint256 endTime,uint256 reservePrice)";
+
+/// `AUCTION_INFO_TYPE` for sellers whose account is a 32-byte Ed25519 public key.
+pub const ED25519_AUCTION_INFO_TYPE: &str = "AuctionInfo(uint256 chainId,uint256 blockNumber,bytes32 seller,uint256 blockspaceSize,uint256 startTime,uint256 endTime,uint256 reservePrice)";
+
+/// Computes the domain separator of a chain. Messages are bound to the `ChainId` they are
+/// meant for, so that a signature for one chain cannot be replayed on another.
+pub fn domain_separator(chain_id: ChainId) -> [u8; 32] {
+    keccak256(&[
+        &keccak256(&[DOMAIN_TYPE.as_bytes()]),
+        &keccak256(&[DOMAIN_NAME.as_bytes()]),
+        &keccak256(&[DOMAIN_VERSION.as_bytes()]),
+        &encode_u64(chain_id),
+    ])
+}
+
+/// Computes the digest to sign for a struct hash in the domain of a chain,
+/// i.e., `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`.
+pub fn typed_data_digest(chain_id: ChainId, struct_hash: &[u8; 32]) -> [u8; 32] {
+    keccak256(&[b"\x19\x01", &domain_separator(chain_id), struct_hash])
+}
+
+/// Computes the EIP-712 digest a bidder signs for a bid.
+pub fn bid_digest(bid: &Bid) -> [u8; 32] {
+    typed_data_digest(bid.chain_id, &hash_bid(bid))
+}
+
+/// Computes the EIP-712 digest a seller signs for an auction.
+pub fn auction_info_digest(auction_info: &AuctionInfo) -> [u8; 32] {
+    typed_data_digest(auction_info.chain_id, &hash_auction_info(auction_info))
+}
+
+/// Returns the typed data of a bid in the JSON format of `eth_signTypedData_v4`. Wallets and
+/// hardware signers display it field by field, and sign the same digest as `bid_digest`.
+/// `uint256` values are decimal strings, since JSON numbers lose precision above 2^53 in
+/// JavaScript signers.
+pub fn bid_typed_data(bid: &Bid) -> Value {
+    let (bidder_type, bidder) = encode_account(&bid.bidder_addr);
+    json!({
+        "types": {
+            "EIP712Domain": domain_fields(),
+            "Bid": [
+                { "name": "chainId", "type": "uint256" },
+                { "name": "auctionId", "type": "string" },
+                { "name": "bidder", "type": bidder_type },
+                { "name": "bidAmount", "type": "uint256" },
+                { "name": "blockspaceSize", "type": "uint256" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "txList", "type": "Tx[]" },
+                { "name": "pricePerGas", "type": "uint256" },
+            ],
+            "Tx": [
+                { "name": "txData", "type": "string" },
+                { "name": "gasLimit", "type": "uint256" },
+            ],
+        },
+        "primaryType": "Bid",
+        "domain": domain(bid.chain_id),
+        "message": {
+            "chainId": bid.chain_id.to_string(),
+            "auctionId": bid.auction_id,
+            "bidder": format!("0x{}", hex::encode(account_value(bidder_type, &bidder))),
+            "bidAmount": bid.bid_amount.to_string(),
+            "blockspaceSize": bid.blockspace_size.to_string(),
+            "nonce": bid.nonce.to_string(),
+            "txList": bid
+                .tx_list
+                .iter()
+                .map(|tx| json!({ "txData": tx.tx_data, "gasLimit": tx.gas_limit.to_string() }))
+                .collect::<Vec<Value>>(),
+            "pricePerGas": bid.price_per_gas.unwrap_or(0).to_string(),
+        },
+    })
+}
+
+/// Returns the typed data of an auction in the JSON format of `eth_signTypedData_v4`.
+pub fn auction_info_typed_data(auction_info: &AuctionInfo) -> Value {
+    let (seller_type, seller) = encode_account(&auction_info.seller_address);
+    json!({
+        "types": {
+            "EIP712Domain": domain_fields(),
+            "AuctionInfo": [
+                { "name": "chainId", "type": "uint256" },
+                { "name": "blockNumber", "type": "uint256" },
+                { "name": "seller", "type": seller_type },
+                { "name": "blockspaceSize", "type": "uint256" },
+                { "name": "startTime", "type": "uint256" },
+                { "name": "endTime", "type": "uint256" },
+                { "name": "reservePrice", "type": "uint256" },
+            ],
+        },
+        "primaryType": "AuctionInfo",
+        "domain": domain(auction_info.chain_id),
+        "message": {
+            "chainId": auction_info.chain_id.to_string(),
+            "blockNumber": auction_info.block_number.to_string(),
+            "seller": format!("0x{}", hex::encode(account_value(seller_type, &seller))),
+            "blockspaceSize": auction_info.blockspace_size.to_string(),
+            "startTime": auction_info.start_time.to_string(),
+            "endTime": auction_info.end_time.to_string(),
+            "reservePrice": auction_info.reserve_price.unwrap_or(0).to_string(),
+        },
+    })
+}
+
+fn hash_bid(bid: &Bid) -> [u8; 32] {
+    let tx_hashes: Vec<u8> = bid.tx_list.iter().flat_map(hash_tx).collect();
+    let (bidder_type, bidder) = encode_account(&bid.bidder_addr);
+    let bid_type = match bidder_type {
+        BYTES32 => ED25519_BID_TYPE,
+        _ => BID_TYPE,
+    };
+    // The type hash covers the referenced `Tx` type as well
+    let type_hash = keccak256(&[bid_type.as_bytes(), TX_TYPE.as_bytes()]);
+    keccak256(&[
+        &type_hash,
+        &encode_u64(bid.chain_id),
+        &keccak256(&[bid.auction_id.as_bytes()]),
+        &bidder,
+        &encode_u64(bid.bid_amount),
+        &encode_u64(bid.blockspace_size),
+        &encode_u64(bid.nonce),
+        &keccak256(&[&tx_hashes]),
+        &encode_u64(bid.price_per_gas.unwrap_or(0)),
+    ])
+}
+
+fn hash_tx(tx: &Tx) -> [u8; 32] {
+    keccak256(&[
+        &keccak256(&[TX_TYPE.as_bytes()]),
+        &keccak256(&[tx.tx_data.as_bytes()]),
+        &encode_u64(tx.gas_limit),
+    ])
+}
+
+fn hash_auction_info(auction_info: &AuctionInfo) -> [u8; 32] {
+    let (seller_type, seller) = encode_account(&auction_info.seller_address);
+    let auction_info_type = match seller_type {
+        BYTES32 => ED25519_AUCTION_INFO_TYPE,
+        _ => AUCTION_INFO_TYPE,
+    };
+    keccak256(&[
+        &keccak256(&[auction_info_type.as_bytes()]),
+        &encode_u64(auction_info.chain_id),
+        &encode_u64(auction_info.block_number),
+        &seller,
+        &encode_u64(auction_info.blockspace_size),
+        &encode_u64(auction_info.start_time),
+        &encode_u64(auction_info.end_time),
+        &encode_u64(auction_info.reserve_price.unwrap_or(0)),
+    ])
+}
+
+fn domain(chain_id: ChainId) -> Value {
+    json!({ "name": DOMAIN_NAME, "version": DOMAIN_VERSION, "chainId": chain_id.to_string() })
+}
+
+fn domain_fields() -> Value {
+    json!([
+        { "name": "name", "type": "string" },
+        { "name": "version", "type": "string" },
+        { "name": "chainId", "type": "uint256" },
+    ])
+}
+
+/// Encodes a `uint256` as a 32-byte big-endian word.
+fn encode_u64(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+const ADDRESS: &str = "address";
+const BYTES32: &str = "bytes32";
+
+/// Encodes an account as a 32-byte word and returns it with its EIP-712 type. 20-byte hex
+/// addresses are `address`es, left-padded with zeros, and 32-byte hex Ed25519 public keys are
+/// `bytes32`, so that the digest commits to the whole key. Any other string is encoded as the
+/// zero address; signatures for it never verify, since no key has that address.
+fn encode_account(account: &str) -> (&'static str, [u8; 32]) {
+    let mut word = [0u8; 32];
+    match hex::decode(account.trim_start_matches("0x")) {
+        Ok(bytes) if bytes.len() == 32 => {
+            word.copy_from_slice(&bytes);
+            (BYTES32, word)
+        }
+        Ok(bytes) if bytes.len() == 20 => {
+            word[12..].copy_from_slice(&bytes);
+            (ADDRESS, word)
+        }
+        _ => (ADDRESS, word),
+    }
+}
+
+/// Returns the bytes of an account word encoded by `encode_account` as they appear in typed data.
+fn account_value<'a>(account_type: &str, word: &'a [u8; 32]) -> &'a [u8] {
+    match account_type {
+        BYTES32 => word,
+        _ => &word[12..],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::signing::{
+        recover_address, Ed25519Key, Secp256k1Key, Secp256k1Verifier, SignatureVerifier,
+    };
+
+    /// Encodes typed data from its JSON alone, the way `eth_signTypedData_v4` signers do.
+    fn encode_typed_data(typed_data: &Value) -> [u8; 32] {
+        let types = &typed_data["types"];
+        let primary_type = typed_data["primaryType"].as_str().unwrap();
+        keccak256(&[
+            b"\x19\x01",
+            &hash_struct(types, "EIP712Domain", &typed_data["domain"]),
+            &hash_struct(types, primary_type, &typed_data["message"]),
+        ])
+    }
+
+    fn hash_struct(types: &Value, name: &str, value: &Value) -> [u8; 32] {
+        let mut encoded = keccak256(&[encode_type(types, name).as_bytes()]).to_vec();
+        for field in types[name].as_array().unwrap() {
+            let field_type = field["type"].as_str().unwrap();
+            let field_value = &value[field["name"].as_str().unwrap()];
+            encoded.extend(encode_value(types, field_type, field_value));
+        }
+        keccak256(&[&encoded])
+    }
+
+    fn encode_type(types: &Value, name: &str) -> String {
+        let mut referenced = Vec::new();
+        collect_types(types, name, &mut referenced);
+        referenced.retain(|referenced| referenced != name);
+        referenced.sort();
+        std::iter::once(name.to_string())
+            .chain(referenced)
+            .map(|name| {
+                let fields: Vec<String> = types[&name]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{} {}",
+                            field["type"].as_str().unwrap(),
+                            field["name"].as_str().unwrap()
+                        )
+                    })
+                    .collect();
+                format!("{}({})", name, fields.join(","))
+            })
+            .collect()
+    }
+
+    fn collect_types(types: &Value, name: &str, found: &mut Vec<String>) {
+        if found.iter().any(|found| found == name) || types.get(name).is_none() {
+            return;
+        }
+        found.push(name.to_string());
+        for field in types[name].as_array().unwrap() {
+            let field_type = field["type"].as_str().unwrap().trim_end_matches("[]");
+            collect_types(types, field_type, found);
+        }
+    }
+
+    fn encode_value(types: &Value, field_type: &str, value: &Value) -> [u8; 32] {
+        if let Some(item_type) = field_type.strip_suffix("[]") {
+            let items: Vec<u8> = value
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(|item| encode_value(types, item_type, item))
+                .collect();
+            return keccak256(&[&items]);
+        }
+        if types.get(field_type).is_some() {
+            return hash_struct(types, field_type, value);
+        }
+        let mut word = [0u8; 32];
+        match field_type {
+            "string" => word = keccak256(&[value.as_str().unwrap().as_bytes()]),
+            "uint256" => {
+                let number: u128 = match value {
+                    Value::String(decimal) => decimal.parse().unwrap(),
+                    _ => value.as_u64().unwrap().into(),
+                };
+                word[16..].copy_from_slice(&number.to_be_bytes());
+            }
+            "address" | "bytes32" => {
+                let bytes = hex::decode(value.as_str().unwrap().trim_start_matches("0x")).unwrap();
+                word[32 - bytes.len()..].copy_from_slice(&bytes);
+            }
+            _ => panic!("unsupported type {}", field_type),
+        }
+        word
+    }
+
+    #[test]
+    fn test_encoder_matches_reference_vector() {
+        // The `Mail` example of the EIP-712 specification, with its published hashes and signature
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" },
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!",
+            },
+        });
+        let types = &typed_data["types"];
+        assert_eq!(
+            encode_type(types, "Mail"),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(hash_struct(types, "EIP712Domain", &typed_data["domain"])),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(hash_struct(types, "Mail", &typed_data["message"])),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        let digest = encode_typed_data(&typed_data);
+        assert_eq!(
+            hex::encode(digest),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        let signature = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+                         07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562\
+                         1c";
+        assert_eq!(
+            recover_address(&digest, signature).unwrap(),
+            "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+    }
+
+    #[test]
+    fn test_typed_data_encodes_to_digest() {
+        let secp256k1_bidder = Secp256k1Key::generate().address();
+        let ed25519_bidder = Ed25519Key::generate().address();
+        for bidder_addr in [secp256k1_bidder, ed25519_bidder] {
+            let bid = Bid {
+                chain_id: 1,
+                auction_id: "auction".to_string(),
+                bidder_addr: bidder_addr.clone(),
+                bid_amount: u64::MAX,
+                blockspace_size: 500,
+                nonce: 7,
+                tx_list: vec![
+                    Tx {
+                        tx_data: "tx1".to_string(),
+                        gas_limit: 200,
+                    },
+                    Tx {
+                        tx_data: "tx2".to_string(),
+                        gas_limit: 300,
+                    },
+                ],
+                price_per_gas: Some(3),
+                ..Default::default()
+            };
+            let typed_data = bid_typed_data(&bid);
+            assert_eq!(typed_data["message"]["bidAmount"], u64::MAX.to_string());
+            assert_eq!(encode_typed_data(&typed_data), bid_digest(&bid));
+
+            let auction_info =
+                AuctionInfo::new(1, 100, bidder_addr, 500, 1000, 2000, String::new())
+                    .with_reserve_price(50);
+            assert_eq!(
+                encode_typed_data(&auction_info_typed_data(&auction_info)),
+                auction_info_digest(&auction_info)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bid_digest_is_bound_to_chain_and_fields() {
+        let key = Secp256k1Key::generate();
+        let mut bid = Bid {
+            chain_id: 1,
+            auction_id: "auction".to_string(),
+            bidder_addr: key.address(),
+            bid_amount: 1000,
+            blockspace_size: 500,
+            tx_list: vec![Tx {
+                tx_data: "tx1".to_string(),
+                gas_limit: 21_000,
+            }],
+            ..Default::default()
+        };
+        bid.bidder_signature = key.sign(&bid_digest(&bid)).unwrap();
+        assert!(Secp256k1Verifier.verify(
+            &bid.bidder_addr,
+            &bid_digest(&bid),
+            &bid.bidder_signature
+        ));
+
+        let other_chain = Bid {
+            chain_id: 2,
+            ..bid.clone()
+        };
+        assert_ne!(bid_digest(&other_chain), bid_digest(&bid));
+        assert_ne!(domain_separator(2), domain_separator(1));
+
+        let other_tx = Bid {
+            tx_list: vec![Tx {
+                tx_data: "tx2".to_string(),
+                gas_limit: 21_000,
+            }],
+            ..bid.clone()
+        };
+        assert!(!Secp256k1Verifier.verify(
+            &bid.bidder_addr,
+            &bid_digest(&other_tx),
+            &bid.bidder_signature
+        ));
+    }
+
+    #[test]
+    fn test_ed25519_accounts_are_encoded_as_bytes32() {
+        let bid = |bidder_addr: String| Bid {
+            chain_id: 1,
+            auction_id: "auction".to_string(),
+            bidder_addr,
+            bid_amount: 1000,
+            ..Default::default()
+        };
+
+        // Keys that share their last 20 bytes still yield different digests
+        let key = Ed25519Key::generate().address();
+        let other_key = format!("{}{}", "11".repeat(12), &key[key.len() - 40..]);
+        assert_ne!(bid_digest(&bid(key.clone())), bid_digest(&bid(other_key)));
+
+        let typed_data = bid_typed_data(&bid(key.clone()));
+        assert_eq!(typed_data["types"]["Bid"][2]["type"], "bytes32");
+        assert_eq!(typed_data["message"]["bidder"], key);
+    }
+
+    #[test]
+    fn test_typed_data_matches_type_strings() {
+        let auction_info = AuctionInfo::new(
+            1,
+            100,
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            500,
+            1000,
+            2000,
+            String::new(),
+        );
+        let typed_data = auction_info_typed_data(&auction_info);
+        assert_eq!(typed_data["primaryType"], "AuctionInfo");
+        assert_eq!(typed_data["domain"]["chainId"], "1");
+        assert_eq!(typed_data["message"]["endTime"], "2000");
+
+        // Rebuilding the type string from the JSON fields yields the one that is hashed
+        let fields: Vec<String> = typed_data["types"]["AuctionInfo"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| {
+                format!(
+                    "{} {}",
+                    field["type"].as_str().unwrap(),
+                    field["name"].as_str().unwrap()
+                )
+            })
+            .collect();
+        assert_eq!(
+            format!("AuctionInfo({})", fields.join(",")),
+            AUCTION_INFO_TYPE
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::{
+    core::domain::{Bid, EncryptedBid},
+    utils::errors::EncryptionError,
+};
+
+/// Domain separation tag for deriving the bid encryption key from the X25519 shared secret.
+const KEY_DERIVATION_TAG: &[u8] = b"lightbulb-bid-encryption-v1";
+
+/// A per-auction X25519 keypair. The public key is published when the auction starts so that
+/// bidders can encrypt their bids to it, and the secret key never leaves the `AuctionWorker`.
+pub struct AuctionKeypair {
+    secret: StaticSecret,
+    public_key: PublicKey,
+}
+
+impl AuctionKeypair {
+    /// Generates a fresh keypair from the operating system's random number generator.
+    pub fn generate() -> Self {
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public_key = PublicKey::from(&secret);
+        AuctionKeypair { secret, public_key }
+    }
+
+    /// Returns the hex-encoded public key.
+    pub fn public_key(&self) -> String {
+        hex::encode(self.public_key.as_bytes())
+    }
+
+    /// Decrypts an encrypted bid and checks that the decrypted bid matches its envelope.
+    pub fn decrypt_bid(&self, encrypted_bid: &EncryptedBid) -> Result<Bid, EncryptionError> {
+        let ephemeral_public_key =
+            PublicKey::from(decode_array::<32>(&encrypted_bid.ephemeral_public_key)?);
+        let nonce = decode_array::<12>(&encrypted_bid.nonce)?;
+        let ciphertext = hex::decode(&encrypted_bid.ciphertext)
+            .map_err(|e| EncryptionError::InvalidEncoding(e.to_string()))?;
+
+        let shared_secret = self.secret.diffie_hellman(&ephemeral_public_key);
+        let cipher = cipher(
+            shared_secret.as_bytes(),
+            &ephemeral_public_key,
+            &self.public_key,
+        );
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &associated_data(encrypted_bid),
+                },
+            )
+            .map_err(|_| EncryptionError::DecryptionFailed)?;
+
+        let bid: Bid = serde_json::from_slice(&plaintext)
+            .map_err(|e| EncryptionError::InvalidPayload(e.to_string()))?;
+        if bid.chain_id != encrypted_bid.chain_id
+            || bid.auction_id != encrypted_bid.auction_id
+            || bid.bidder_addr != encrypted_bid.bidder_addr
+        {
+            return Err(EncryptionError::EnvelopeMismatch);
+        }
+
+        Ok(bid)
+    }
+}
+
+/// Encrypts a bid to the public key of an auction. Used by bidders before submitting a bid
+/// to an auction running in `AuctionMode::Encrypted`.
+///
+/// A fresh ephemeral X25519 key is agreed with the auction key, and the serialized bid is
+/// sealed with ChaCha20-Poly1305. The chain ID, auction ID, and bidder address stay in the clear
+/// and are authenticated as associated data.
+pub fn encrypt_bid(bid: &Bid, auction_public_key: &str) -> Result<EncryptedBid, EncryptionError> {
+    let auction_public_key = PublicKey::from(decode_array::<32>(auction_public_key)?);
+    let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+    let ephemeral_public_key = PublicKey::from(&ephemeral_secret);
+
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+
+    let mut encrypted_bid = EncryptedBid {
+        chain_id: bid.chain_id,
+        auction_id: bid.auction_id.clone(),
+        bidder_addr: bid.bidder_addr.clone(),
+        ephemeral_public_key: hex::encode(ephemeral_public_key.as_bytes()),
+        nonce: hex::encode(nonce),
+        ..Default::default()
+    };
+
+    let plaintext =
+        serde_json::to_vec(bid).map_err(|e| EncryptionError::InvalidPayload(e.to_string()))?;
+    let shared_secret = ephemeral_secret.diffie_hellman(&auction_public_key);
+    let cipher = cipher(
+        shared_secret.as_bytes(),
+        &ephemeral_public_key,
+        &auction_public_key,
+    );
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &associated_data(&encrypted_bid),
+            },
+        )
+        .map_err(|_| EncryptionError::EncryptionFailed)?;
+
+    encrypted_bid.ciphertext = hex::encode(ciphertext);
+    Ok(encrypted_bid)
+}
+
+/// Derives the symmetric cipher from the X25519 shared secret and both public keys.
+fn cipher(
+    shared_secret: &[u8; 32],
+    ephemeral_public_key: &PublicKey,
+    auction_public_key: &PublicKey,
+) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_TAG);
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_public_key.as_bytes());
+    hasher.update(auction_public_key.as_bytes());
+    let key = hasher.finalize();
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+/// The envelope fields that stay in the clear but must not be tampered with.
+fn associated_data(encrypted_bid: &EncryptedBid) -> Vec<u8> {
+    let mut aad = encrypted_bid.chain_id.to_be_bytes().to_vec();
+    aad.extend_from_slice(encrypted_bid.auction_id.as_bytes());
+    aad.extend_from_slice(encrypted_bid.bidder_addr.as_bytes());
+    aad
+}
+
+fn decode_array<const N: usize>(value: &str) -> Result<[u8; N], EncryptionError> {
+    let bytes = hex::decode(value).map_err(|e| EncryptionError::InvalidEncoding(e.to_string()))?;
+    bytes
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidEncoding(format!("expected {} bytes", N)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::Tx;
+
+    fn bid() -> Bid {
+        Bid {
+            chain_id: 1,
+            auction_id: "auction".to_string(),
+            bidder_addr: "0xBidder1".to_string(),
+            bid_amount: 1000,
+            blockspace_size: 500,
+            bidder_signature: "0xBidder1Signature".to_string(),
+            tx_list: vec![Tx {
+                tx_data: "tx1".to_string(),
+                gas_limit: 21_000,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_encrypted_bid_round_trip() {
+        let keypair = AuctionKeypair::generate();
+        let encrypted_bid = encrypt_bid(&bid(), &keypair.public_key()).unwrap();
+
+        assert!(!encrypted_bid.ciphertext.contains("0xBidder1Signature"));
+
+        let decrypted = keypair.decrypt_bid(&encrypted_bid).unwrap();
+        assert_eq!(decrypted.hash(), bid().hash());
+    }
+
+    #[test]
+    fn test_decryption_fails_with_other_key_or_tampered_envelope() {
+        let keypair = AuctionKeypair::generate();
+        let encrypted_bid = encrypt_bid(&bid(), &keypair.public_key()).unwrap();
+
+        let other_keypair = AuctionKeypair::generate();
+        assert!(matches!(
+            other_keypair.decrypt_bid(&encrypted_bid),
+            Err(EncryptionError::DecryptionFailed)
+        ));
+
+        let mut tampered = encrypted_bid.clone();
+        tampered.bidder_addr = "0xBidder2".to_string();
+        assert!(matches!(
+            keypair.decrypt_bid(&tampered),
+            Err(EncryptionError::DecryptionFailed)
+        ));
+    }
+}
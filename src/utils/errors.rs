@@ -37,6 +37,75 @@ pub enum AuctionError {
 
     #[error("Auction has already ended")]
     AuctionEnded,
+
+    #[error("The previous auction is still being settled")]
+    AuctionSettling,
+
+    #[error("Auction is not waiting to be settled")]
+    AuctionNotSettling,
+
+    #[error("Bid amount {bid_amount} is below the reserve price {reserve_price}")]
+    BidBelowReservePrice { bid_amount: u64, reserve_price: u64 },
+
+    #[error("Bid is too low: the current price is {current_price}, bid at least {minimum_bid}")]
+    BidTooLow {
+        current_price: u64,
+        minimum_bid: u64,
+    },
+
+    #[error("Bids are sealed until the auction ends")]
+    SealedBidAuction,
+
+    #[error("This auction only accepts bid commitments; reveal the bid once bidding closes")]
+    CommitmentRequired,
+
+    #[error("This auction does not accept bid commitments")]
+    CommitmentNotAccepted,
+
+    #[error("Auction is not in its reveal phase")]
+    NotInRevealPhase,
+
+    #[error("Revealed bid does not match any commitment of the bidder")]
+    InvalidReveal,
+
+    #[error("This auction only accepts bids encrypted to the auction public key")]
+    EncryptedBidRequired,
+
+    #[error("This auction does not accept encrypted bids")]
+    EncryptedBidNotAccepted,
+
+    #[error("Bid nonce {nonce} is not higher than the bidder's current nonce {current_nonce}")]
+    StaleBidNonce { nonce: u64, current_nonce: u64 },
+
+    #[error("No matching bid found: {0}")]
+    BidNotFound(String),
+
+    #[error("Bidder already has the maximum of {limit} bid(s) in this auction")]
+    BidderBidLimitReached { limit: usize },
+
+    #[error("Auction already has the maximum of {limit} bid(s)")]
+    AuctionBidLimitReached { limit: usize },
+
+    #[error("The gas limits of the bid's transactions overflow")]
+    GasOverflow,
+
+    #[error("A per-gas bid must have a non-zero price per gas")]
+    ZeroPricePerGas,
+
+    #[error("Invalid package bid: {0}")]
+    InvalidPackageBid(String),
+
+    #[error("This chain does not accept package bids")]
+    PackageBidNotAccepted,
+
+    #[error("Invalid cross-chain bid: {0}")]
+    InvalidCrossChainBid(String),
+
+    #[error("{0} is not the seller of this auction")]
+    NotAuctionSeller(String),
+
+    #[error("Failed to sign bid receipt: {0}")]
+    ReceiptSigningFailed(String),
 }
 
 /// A set of possible errors that can occur in the registry workflow.
@@ -59,6 +128,9 @@ pub enum RegistryError {
 
     #[error("Chain {0} is already registered")]
     ChainAlreadyRegistered(ChainId),
+
+    #[error("Invalid reserve price: {0}")]
+    InvalidReservePrice(u64),
 }
 
 #[derive(Error, Debug)]
@@ -108,6 +180,94 @@ impl From<AuctionError> for BidError {
     }
 }
 
+/// A set of possible errors that can occur when encrypting or decrypting bids.
+#[derive(Error, Debug)]
+pub enum EncryptionError {
+    #[error("Invalid encoding: {0}")]
+    InvalidEncoding(String),
+
+    #[error("Invalid bid payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Failed to encrypt bid")]
+    EncryptionFailed,
+
+    #[error("Failed to decrypt bid")]
+    DecryptionFailed,
+
+    #[error("Decrypted bid does not match its envelope")]
+    EnvelopeMismatch,
+}
+
+/// A set of possible errors that can occur when creating or checking signatures.
+#[derive(Error, Debug)]
+pub enum SignatureError {
+    #[error("Invalid encoding: {0}")]
+    InvalidEncoding(String),
+
+    #[error("Invalid signature")]
+    InvalidSignature,
+
+    #[error("Failed to sign")]
+    SigningFailed,
+}
+
+/// A set of possible errors that can occur when loading or saving an operator keystore.
+#[derive(Error, Debug)]
+pub enum KeystoreError {
+    #[error("Keystore I/O error: {0}")]
+    Io(String),
+
+    #[error("Invalid keystore: {0}")]
+    InvalidFormat(String),
+
+    #[error("Wrong password or corrupted keystore")]
+    DecryptionFailed,
+
+    #[error("Keystore key does not match its address {0}")]
+    AddressMismatch(String),
+}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+/// A set of possible errors that can occur when writing or verifying the audit log.
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("Audit log I/O error: {0}")]
+    Io(String),
+
+    #[error("Malformed audit log entry on line {0}")]
+    MalformedEntry(usize),
+
+    #[error("Audit log chain is broken at entry {0}")]
+    BrokenChain(u64),
+
+    #[error("Audit log is truncated: expected at least {expected} entries, found {found}")]
+    Truncated { expected: u64, found: u64 },
+
+    #[error("Audit log entry {0} is not signed by the operator")]
+    InvalidSignature(u64),
+
+    #[error("Signed audit head at {0} entries has an invalid signature")]
+    InvalidHeadSignature(u64),
+
+    #[error("Audit log entry {0} was not written, because an earlier entry failed to be")]
+    EarlierWriteFailed(u64),
+
+    #[error("Failed to sign audit record: {0}")]
+    Signing(String),
+}
+
+impl From<std::io::Error> for AuditError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum DatabaseError {
     #[error("Database error: {0}")]
@@ -1,6 +1,7 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
 /// Returns the current Unix timestamp in milliseconds.
 pub fn current_unix_ms() -> u64 {
@@ -8,12 +9,6 @@ pub fn current_unix_ms() -> u64 {
     now.as_millis() as u64
 }
 
-/// Mock function for signature verification. Always returns `true` in this demo.
-/// Replace with a real cryptographic check in production.
-pub fn verify_signature(_addr: &str, _signature: &str) -> bool {
-    true
-}
-
 /// Computes a SHA-256 hash of the provided inputs and returns the result as a hex-encoded string.
 pub fn compute_hash(inputs: &[&[u8]]) -> String {
     let mut hasher = Sha256::new();
@@ -23,3 +18,25 @@ pub fn compute_hash(inputs: &[&[u8]]) -> String {
     let result = hasher.finalize();
     hex::encode(result)
 }
+
+/// Computes a SHA-256 hash like `compute_hash`, but prefixes every input with its length, so
+/// that no two different sequences of inputs feed the same bytes to the hasher.
+pub fn compute_framed_hash(inputs: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for input in inputs {
+        hasher.update((input.len() as u64).to_be_bytes());
+        hasher.update(input);
+    }
+    let result = hasher.finalize();
+    hex::encode(result)
+}
+
+/// Computes a Keccak-256 hash of the provided inputs. Used for the message hashes that bidders,
+/// sellers, and the operator sign.
+pub fn keccak256(inputs: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    hasher.finalize().into()
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::helpers::keccak256;
+
+/// Prefixed to leaves and inner nodes before hashing, so that an inner node can never be passed
+/// off as a leaf.
+const LEAF_PREFIX: &[u8] = &[0x00];
+const NODE_PREFIX: &[u8] = &[0x01];
+
+/// A binary Keccak-256 Merkle tree.
+///
+/// Leaves are hashed as `keccak256(0x00 || leaf)` and inner nodes as
+/// `keccak256(0x01 || left || right)`. The last node of a level with an odd number of nodes is
+/// promoted to the next level as is. The root of an empty tree is 32 zero bytes.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// The node hashes of each level, from the leaves up to the root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+/// The side of the path a sibling hash sits on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MerkleSide {
+    Left,
+    Right,
+}
+
+/// A sibling hash on the path from a leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProofNode {
+    /// Hex-encoded hash of the sibling.
+    pub hash: String,
+    pub side: MerkleSide,
+}
+
+/// Proves that a leaf is included in a `MerkleTree`, given only its root (see `verify_proof`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The sibling hashes from the leaf level up. Levels where the node was promoted have none.
+    pub siblings: Vec<MerkleProofNode>,
+}
+
+impl MerkleTree {
+    /// Builds a tree over the given leaves, in the given order.
+    pub fn new<T: AsRef<[u8]>>(leaves: &[T]) -> Self {
+        let mut levels = vec![leaves
+            .iter()
+            .map(|leaf| hash_leaf(leaf.as_ref()))
+            .collect::<Vec<_>>()];
+
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next_level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next_level);
+        }
+
+        MerkleTree { levels }
+    }
+
+    /// Returns the hex-encoded root hash.
+    pub fn root(&self) -> String {
+        let root = self
+            .levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or([0u8; 32]);
+        hex::encode(root)
+    }
+
+    /// Returns the number of leaves.
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns the inclusion proof of a leaf, or `None` if the leaf is not in the tree.
+    pub fn proof(&self, leaf: &[u8]) -> Option<MerkleProof> {
+        let leaf_hash = hash_leaf(leaf);
+        let mut index = self.levels[0].iter().position(|hash| *hash == leaf_hash)?;
+
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            if let Some(sibling) = level.get(sibling_index) {
+                siblings.push(MerkleProofNode {
+                    hash: hex::encode(sibling),
+                    side: if sibling_index < index {
+                        MerkleSide::Left
+                    } else {
+                        MerkleSide::Right
+                    },
+                });
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof { siblings })
+    }
+}
+
+/// Checks that a leaf is included in the tree with the given hex-encoded root. Needs nothing
+/// but the leaf, the proof, and the root, so that anyone can audit a published commitment.
+pub fn verify_proof(leaf: &[u8], proof: &MerkleProof, root: &str) -> bool {
+    let mut hash = hash_leaf(leaf);
+    for node in &proof.siblings {
+        let Some(sibling) = hex::decode(&node.hash)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        else {
+            return false;
+        };
+        hash = match node.side {
+            MerkleSide::Left => hash_node(&sibling, &hash),
+            MerkleSide::Right => hash_node(&hash, &sibling),
+        };
+    }
+    hex::encode(hash).eq_ignore_ascii_case(root)
+}
+
+fn hash_leaf(leaf: &[u8]) -> [u8; 32] {
+    keccak256(&[LEAF_PREFIX, leaf])
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    keccak256(&[NODE_PREFIX, left, right])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_leaf_has_a_valid_proof() {
+        for leaf_count in 1..=7 {
+            let leaves: Vec<String> = (0..leaf_count).map(|i| format!("leaf{}", i)).collect();
+            let tree = MerkleTree::new(&leaves);
+            assert_eq!(tree.leaf_count(), leaf_count);
+
+            for leaf in &leaves {
+                let proof = tree.proof(leaf.as_bytes()).unwrap();
+                assert!(verify_proof(leaf.as_bytes(), &proof, &tree.root()));
+                assert!(!verify_proof(b"other", &proof, &tree.root()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_is_bound_to_leaf_and_root() {
+        let tree = MerkleTree::new(&["a", "b", "c"]);
+        assert!(tree.proof(b"d").is_none());
+
+        let proof = tree.proof(b"c").unwrap();
+        let other_root = MerkleTree::new(&["a", "b", "d"]).root();
+        assert!(!verify_proof(b"c", &proof, &other_root));
+
+        // Swapping the side of a sibling changes the path
+        let mut tampered = tree.proof(b"a").unwrap();
+        tampered.siblings[0].side = MerkleSide::Left;
+        assert!(!verify_proof(b"a", &tampered, &tree.root()));
+
+        assert_eq!(MerkleTree::new::<&str>(&[]).root(), hex::encode([0u8; 32]));
+    }
+}
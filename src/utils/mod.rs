@@ -1,3 +1,7 @@
+pub mod eip712;
+pub mod encryption;
 pub mod errors;
 pub mod helpers;
+pub mod merkle;
+pub mod signing;
 pub mod types;
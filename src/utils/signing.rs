@@ -0,0 +1,229 @@
+use ed25519_dalek::Signer;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use sha3::{Digest, Keccak256};
+
+use crate::utils::errors::SignatureError;
+
+/// Checks that a signature over a 32-byte message hash was made by the key behind an address.
+/// Each chain selects an implementation through its `SignatureScheme`, so that bidders and
+/// sellers can sign with the kind of account their chain uses.
+pub trait SignatureVerifier: Send + Sync {
+    fn verify(&self, addr: &str, message_hash: &[u8; 32], signature: &str) -> bool;
+}
+
+/// Verifies secp256k1 signatures by Ethereum-style addresses, by recovering the signer's address
+/// from the `r || s || v` signature. Addresses are compared case-insensitively.
+pub struct Secp256k1Verifier;
+
+impl SignatureVerifier for Secp256k1Verifier {
+    fn verify(&self, addr: &str, message_hash: &[u8; 32], signature: &str) -> bool {
+        recover_address(message_hash, signature)
+            .is_ok_and(|signer| signer.eq_ignore_ascii_case(addr))
+    }
+}
+
+/// Verifies Ed25519 signatures by addresses that are hex-encoded Ed25519 public keys.
+/// Verification is strict: weak public keys and non-canonical signature bytes are rejected.
+/// The hex encoding of a signature may still be spelled in several ways (case, `0x` prefix), so
+/// identities such as `Bid::hash` never depend on the signature string.
+pub struct Ed25519Verifier;
+
+impl SignatureVerifier for Ed25519Verifier {
+    fn verify(&self, addr: &str, message_hash: &[u8; 32], signature: &str) -> bool {
+        let Ok(public_key) = decode_array::<32>(addr) else {
+            return false;
+        };
+        let Ok(signature) = decode_array::<64>(signature) else {
+            return false;
+        };
+        ed25519_dalek::VerifyingKey::from_bytes(&public_key).is_ok_and(|verifying_key| {
+            verifying_key
+                .verify_strict(
+                    message_hash,
+                    &ed25519_dalek::Signature::from_bytes(&signature),
+                )
+                .is_ok()
+        })
+    }
+}
+
+/// A secp256k1 signing key. The Lightbulb operator signs finalized auction outcomes with one, so
+/// that sequencers and bidders can verify them without trusting the channel they were received
+/// over, and bidders and sellers sign their bids and auctions with theirs.
+pub struct Secp256k1Key {
+    signing_key: SigningKey,
+}
+
+impl Secp256k1Key {
+    /// Generates a fresh key from the operating system's random number generator.
+    pub fn generate() -> Self {
+        Secp256k1Key {
+            signing_key: SigningKey::random(&mut OsRng),
+        }
+    }
+
+    /// Loads a key from its hex-encoded 32-byte secret scalar.
+    pub fn from_hex(secret_key: &str) -> Result<Self, SignatureError> {
+        let bytes = hex::decode(secret_key.trim_start_matches("0x"))
+            .map_err(|e| SignatureError::InvalidEncoding(e.to_string()))?;
+        let signing_key = SigningKey::from_slice(&bytes)
+            .map_err(|_| SignatureError::InvalidEncoding("invalid secret key".to_string()))?;
+        Ok(Secp256k1Key { signing_key })
+    }
+
+    /// Returns the Ethereum-style address of the key.
+    pub fn address(&self) -> String {
+        address_of(self.signing_key.verifying_key())
+    }
+
+    /// Returns the hex-encoded compressed public key.
+    pub fn public_key(&self) -> String {
+        let public_key = self.signing_key.verifying_key().to_encoded_point(true);
+        format!("0x{}", hex::encode(public_key.as_bytes()))
+    }
+
+    /// Returns the 32-byte secret scalar, e.g., to store the key in an encrypted keystore.
+    pub(crate) fn secret_key_bytes(&self) -> [u8; 32] {
+        self.signing_key.to_bytes().into()
+    }
+
+    /// Signs a 32-byte digest and returns the hex-encoded 65-byte `r || s || v` signature.
+    pub fn sign(&self, digest: &[u8; 32]) -> Result<String, SignatureError> {
+        let (signature, recovery_id) = self
+            .signing_key
+            .sign_prehash_recoverable(digest)
+            .map_err(|_| SignatureError::SigningFailed)?;
+
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(27 + recovery_id.to_byte());
+        Ok(format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+/// An Ed25519 signing key, for chains whose accounts use `SignatureScheme::Ed25519`.
+pub struct Ed25519Key {
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl Ed25519Key {
+    /// Generates a fresh key from the operating system's random number generator.
+    pub fn generate() -> Self {
+        let mut secret_key = [0u8; 32];
+        OsRng.fill_bytes(&mut secret_key);
+        Ed25519Key {
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&secret_key),
+        }
+    }
+
+    /// Loads a key from its hex-encoded 32-byte secret key.
+    pub fn from_hex(secret_key: &str) -> Result<Self, SignatureError> {
+        Ok(Ed25519Key {
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&decode_array::<32>(secret_key)?),
+        })
+    }
+
+    /// Returns the address of the key, i.e., its hex-encoded public key.
+    pub fn address(&self) -> String {
+        format!(
+            "0x{}",
+            hex::encode(self.signing_key.verifying_key().as_bytes())
+        )
+    }
+
+    /// Signs a 32-byte digest and returns the hex-encoded 64-byte signature.
+    pub fn sign(&self, digest: &[u8; 32]) -> String {
+        format!(
+            "0x{}",
+            hex::encode(self.signing_key.sign(digest).to_bytes())
+        )
+    }
+}
+
+/// Recovers the address that signed a 32-byte digest from a hex-encoded `r || s || v` signature.
+pub fn recover_address(digest: &[u8; 32], signature: &str) -> Result<String, SignatureError> {
+    let bytes = hex::decode(signature.trim_start_matches("0x"))
+        .map_err(|e| SignatureError::InvalidEncoding(e.to_string()))?;
+    if bytes.len() != 65 {
+        return Err(SignatureError::InvalidEncoding(
+            "expected 65 bytes".to_string(),
+        ));
+    }
+
+    let signature =
+        Signature::from_slice(&bytes[..64]).map_err(|_| SignatureError::InvalidSignature)?;
+    // Accept both the raw recovery ID and the Ethereum-style `v` of 27 or 28
+    let recovery_id =
+        RecoveryId::from_byte(bytes[64] % 27).ok_or(SignatureError::InvalidSignature)?;
+    let verifying_key = VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+        .map_err(|_| SignatureError::InvalidSignature)?;
+    Ok(address_of(&verifying_key))
+}
+
+/// Decodes a hex-encoded 32-byte digest, such as the hash of an auction result.
+pub fn decode_digest(digest: &str) -> Result<[u8; 32], SignatureError> {
+    decode_array::<32>(digest)
+}
+
+fn decode_array<const N: usize>(value: &str) -> Result<[u8; N], SignatureError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| SignatureError::InvalidEncoding(e.to_string()))?
+        .try_into()
+        .map_err(|_| SignatureError::InvalidEncoding(format!("expected {} bytes", N)))
+}
+
+/// The last 20 bytes of the Keccak-256 hash of the uncompressed public key.
+fn address_of(verifying_key: &VerifyingKey) -> String {
+    let public_key = verifying_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_recover_address() {
+        let key = Secp256k1Key::generate();
+        let digest = [7u8; 32];
+        let signature = key.sign(&digest).unwrap();
+
+        assert_eq!(recover_address(&digest, &signature).unwrap(), key.address());
+        assert_ne!(
+            recover_address(&[8u8; 32], &signature).unwrap(),
+            key.address()
+        );
+    }
+
+    #[test]
+    fn test_verifiers_only_accept_their_own_scheme() {
+        let digest = [7u8; 32];
+        let secp256k1_key = Secp256k1Key::generate();
+        let secp256k1_signature = secp256k1_key.sign(&digest).unwrap();
+        let ed25519_key = Ed25519Key::generate();
+        let ed25519_signature = ed25519_key.sign(&digest);
+
+        assert!(Secp256k1Verifier.verify(&secp256k1_key.address(), &digest, &secp256k1_signature));
+        assert!(Ed25519Verifier.verify(&ed25519_key.address(), &digest, &ed25519_signature));
+
+        assert!(!Ed25519Verifier.verify(&ed25519_key.address(), &[8u8; 32], &ed25519_signature));
+        assert!(!Ed25519Verifier.verify(
+            &Ed25519Key::generate().address(),
+            &digest,
+            &ed25519_signature
+        ));
+        assert!(!Secp256k1Verifier.verify(&ed25519_key.address(), &digest, &ed25519_signature));
+        assert!(!Ed25519Verifier.verify(&secp256k1_key.address(), &digest, &secp256k1_signature));
+    }
+
+    #[test]
+    fn test_address_of_known_key() {
+        // The first well-known development account of Hardhat and Anvil
+        let key = Secp256k1Key::from_hex(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        assert_eq!(key.address(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+    }
+}
@@ -5,8 +5,13 @@ use lightbulb::{
         auction::AuctionManager,
         domain::{AuctionInfo, Bid, ChainId, ChainInfo, Tx},
     },
-    services::{bid::BidService, registry::RegistryService},
-    utils::helpers::current_unix_ms,
+    services::{
+        audit::{self, AuditLog},
+        bid::BidService,
+        operator::{Keystore, OperatorService, DEFAULT_ROTATION_OVERLAP_MS},
+        registry::RegistryService,
+    },
+    utils::{helpers::current_unix_ms, signing::Secp256k1Key},
 };
 use tokio::time::{sleep, Duration};
 
@@ -14,10 +19,28 @@ use tokio::time::{sleep, Duration};
 async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
     println!("Starting test: test_auction_lifecycle");
 
-    // 1. Setup RegistryService
+    // 1. Setup RegistryService, recording lifecycle events in an audit log signed with the
+    // operator key of a keystore
+    let operator_key = Secp256k1Key::generate();
+    let keystore_path = std::env::temp_dir().join(format!(
+        "lightbulb-keystore-integration-{}.json",
+        current_unix_ms()
+    ));
+    Keystore::encrypt(&operator_key, "operator password", 4)?.save(&keystore_path)?;
+    let operator = OperatorService::from_keystore(
+        &keystore_path,
+        "operator password",
+        DEFAULT_ROTATION_OVERLAP_MS,
+    )?;
+    std::fs::remove_file(&keystore_path)?;
+    let audit_log_path = std::env::temp_dir().join(format!(
+        "lightbulb-audit-integration-{}.log",
+        current_unix_ms()
+    ));
+    let audit_log = AuditLog::open(&audit_log_path, operator.clone())?;
     let registry_service = {
         let (auction_registry, chain_registry) = RegistryService::create_registry().await;
-        RegistryService::new(auction_registry, chain_registry)
+        RegistryService::new(auction_registry, chain_registry).with_audit_log(audit_log.clone())
     };
 
     println!("RegistryService created");
@@ -25,17 +48,22 @@ async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
     // Define the ChainId to use for the test
     let test_chain_id: ChainId = 1;
 
+    // Sellers sign their auctions and bidders sign their bids
+    let seller_key = Secp256k1Key::generate();
+    let bidder_keys: Vec<Secp256k1Key> = (0..3).map(|_| Secp256k1Key::generate()).collect();
+
     registry_service
         .register_chain(
             test_chain_id,
             ChainInfo {
                 gas_limit: 1000,
-                registered_sellers: vec!["0xTestSeller".to_string()],
+                registered_sellers: vec![seller_key.address()],
+                ..Default::default()
             },
         )
         .await?;
 
-    println!("ChainId 1 registered with seller 0xTestSeller");
+    println!("ChainId 1 registered with seller {}", seller_key.address());
 
     let chain_ids = registry_service.get_chain_ids().await;
     assert_eq!(chain_ids.len(), 1, "ChainId registration failed");
@@ -46,7 +74,7 @@ async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
     println!("ChainIds registered: {:?}", chain_ids);
 
     // 2. Setup AuctionManager and BidService
-    let auction_manager = Arc::new(AuctionManager::new(&registry_service).await);
+    let auction_manager = Arc::new(AuctionManager::new(&registry_service, operator.clone()).await);
     println!("AuctionManager created");
 
     let mut flush_intervals: HashMap<ChainId, Duration> = HashMap::new();
@@ -63,16 +91,28 @@ async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
     let auction_info = AuctionInfo::new(
         test_chain_id,
         100, // block_number
-        "0xTestSeller".to_string(),
+        seller_key.address(),
         500,        // blockspace_size
         now - 1000, // start_time: 1 second in the past
         now + 5000, // end_time: 5 seconds in the future
-        "0xSellerSignature".to_string(),
+        String::new(),
     );
+    let seller_signature = seller_key.sign(&auction_info.signing_hash())?;
+    let auction_info = auction_info.with_seller_signature(seller_signature);
 
     println!("AuctionInfo created: {:?}", auction_info);
 
-    // 4. Submit AuctionInfo to the registry
+    // 4. Submit AuctionInfo to the registry, after checking that a tampered copy is rejected
+    let mut forged_auction_info = auction_info.clone();
+    forged_auction_info.blockspace_size = 1000;
+    assert!(
+        registry_service
+            .submit_auction_info(forged_auction_info)
+            .await
+            .is_err(),
+        "AuctionInfo with a forged signature was accepted"
+    );
+
     registry_service
         .submit_auction_info(auction_info.clone())
         .await?;
@@ -95,54 +135,98 @@ async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // 6. Submit bids via BidService
-    let bid1 = Bid {
+    let mut bid1 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder1".to_string(),
+        bidder_addr: bidder_keys[0].address(),
         bid_amount: 1000,
-        bidder_signature: "0xBidder1Signature".to_string(),
+        blockspace_size: 500, // consumes the whole blockspace
         tx_list: vec![Tx {
             tx_data: "tx1".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
-    let bid2 = Bid {
+    let mut bid2 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder2".to_string(),
+        bidder_addr: bidder_keys[1].address(),
         bid_amount: 1500, // Highest bid
-        bidder_signature: "0xBidder2Signature".to_string(),
+        blockspace_size: 500,
         tx_list: vec![Tx {
             tx_data: "tx2".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
-    let bid3 = Bid {
+    let mut bid3 = Bid {
         chain_id: test_chain_id,
         auction_id: auction_id.clone(),
-        bidder_addr: "0xBidder3".to_string(),
+        bidder_addr: bidder_keys[2].address(),
         bid_amount: 1200,
-        bidder_signature: "0xBidder3Signature".to_string(),
+        blockspace_size: 500,
         tx_list: vec![Tx {
             tx_data: "tx3".to_string(),
+            gas_limit: 500,
         }],
+        ..Default::default()
     };
 
+    for (bid, bidder_key) in [&mut bid1, &mut bid2, &mut bid3]
+        .into_iter()
+        .zip(bidder_keys.iter())
+    {
+        bid.bidder_signature = bidder_key.sign(&bid.signing_hash())?;
+    }
+
     println!("Bids created");
 
-    bid_service.store_bid(bid1.clone()).await?;
+    // A bid signed by someone other than its bidder is rejected
+    let mut forged_bid = bid1.clone();
+    forged_bid.bid_amount = 2000;
+    assert!(
+        bid_service.store_bid(forged_bid).await.is_err(),
+        "Bid with a forged signature was accepted"
+    );
+
+    let receipt1 = bid_service.store_bid(bid1.clone()).await?;
     println!("Bid1 stored successfully");
 
     sleep(Duration::from_secs(1)).await;
 
-    bid_service.store_bid(bid2.clone()).await?;
+    let receipt2 = bid_service.store_bid(bid2.clone()).await?;
     println!("Bid2 stored successfully");
 
     sleep(Duration::from_secs(1)).await;
 
-    bid_service.store_bid(bid3.clone()).await?;
+    let receipt3 = bid_service.store_bid(bid3.clone()).await?;
     println!("Bid3 stored successfully");
 
+    // Each bid comes with a receipt signed by the operator
+    let operator_address = auction_manager.get_operator_address().await;
+    assert_eq!(operator_address, operator_key.address());
+    for (sequence, (receipt, bid)) in [(&receipt1, &bid1), (&receipt2, &bid2), (&receipt3, &bid3)]
+        .into_iter()
+        .enumerate()
+    {
+        assert!(
+            receipt.verify(&operator_address),
+            "Bid receipt does not verify"
+        );
+        assert_eq!(receipt.sequence, sequence as u64);
+        assert_eq!(receipt.bidder_addr, bid.bidder_addr);
+    }
+    assert!(receipt1.arrival_time < receipt2.arrival_time);
+
+    let mut tampered_receipt = receipt1.clone();
+    tampered_receipt.arrival_time += 1;
+    assert!(
+        !tampered_receipt.verify(&operator_address),
+        "Tampered bid receipt verifies"
+    );
+
     // 7. Wait for the auction to end
     println!("Waiting for auction to end...");
     sleep(Duration::from_secs(6)).await;
@@ -162,28 +246,60 @@ async fn test_auction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
         Ok(state) => {
             assert!(state.is_ended, "Auction state is not marked as ended");
             assert_eq!(
-                state.winner,
-                Some("0xBidder2".to_string()),
+                state.winners.len(),
+                1,
+                "Only one bid should fit into the blockspace"
+            );
+            assert_eq!(
+                state.winners[0].bidder_addr,
+                bidder_keys[1].address(),
                 "Winner does not match the highest bid"
             );
             assert_eq!(
                 state.highest_bid, 1500,
                 "Highest bid amount does not match the expected value"
             );
+            assert_eq!(
+                state.clearing_price, 1500,
+                "Clearing price does not match the first-price rule"
+            );
             assert_eq!(
                 state.bids.len(),
                 3,
                 "Number of bids does not match the expected count"
             );
+            let mut sequences: Vec<u64> = state.bids.iter().map(|bid| bid.sequence).collect();
+            sequences.sort_unstable();
+            assert_eq!(
+                sequences,
+                vec![0, 1, 2],
+                "Bids were not assigned consecutive arrival sequence numbers"
+            );
             println!(
                 "Auction state verified: Winner: {}, Highest Bid: {}",
-                state.winner.unwrap(),
-                state.highest_bid
+                state.winners[0].bidder_addr, state.highest_bid
             );
         }
         Err(e) => panic!("Failed to retrieve auction state: {}", e),
     }
 
+    // 10. Verify the audit log: chain registered, auction queued and started, 3 bids, auction ended
+    let audit_head = audit_log.head().await;
+    assert_eq!(
+        audit_head.entry_count, 7,
+        "Unexpected number of audit log entries"
+    );
+    assert_eq!(
+        audit::verify_log(
+            &audit_log_path,
+            Some(&audit_head),
+            Some(&[operator.address().await])
+        )?,
+        audit_head
+    );
+    std::fs::remove_file(&audit_log_path)?;
+    std::fs::remove_file(audit::heads_path(&audit_log_path))?;
+
     println!("Test completed successfully");
     Ok(())
 }